@@ -1,3 +1,4 @@
 pub mod def;
 pub mod database;
-pub mod medium;
\ No newline at end of file
+pub mod medium;
+pub mod evaluate;
\ No newline at end of file
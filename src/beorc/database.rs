@@ -1,11 +1,22 @@
 use std::fs;
+use std::fs::File;
+use std::io;
+use std::io::{Read, Write};
 use std::env::current_dir;
 use std::path::Path;
 
 use crate::Vector2;
+use crate::close_enough_f64;
 
 use crate::DefinitionUnit;
 use crate::beorc::def::Trace;
+use crate::beorc::def::TraceDescriptors;
+use crate::beorc::def::DESCRIPTOR_VALUES;
+use crate::beorc::def::STORED_DECIMALS_FACTOR;
+
+/// Number of values the precise trace and average take on the quick access database, after the descriptors
+///
+pub const PRECISE_VALUES: usize = 4;
 
 #[derive(Clone)]
 pub struct QuickTrace {
@@ -13,6 +24,11 @@ pub struct QuickTrace {
 
     pub trace: Vector2<i64>,
     pub average: Vector2<i64>,
+
+    pub precise_trace: Vector2<f64>,
+    pub precise_average: Vector2<f64>,
+
+    pub descriptors: TraceDescriptors,
 }
 
 impl QuickTrace {
@@ -21,14 +37,35 @@ impl QuickTrace {
             id: String::from("Untreated trace"),
             trace: Vector2::new(0, 0),
             average: Vector2::new(0, 0),
+            precise_trace: Vector2::new(0.0, 0.0),
+            precise_average: Vector2::new(0.0, 0.0),
+            descriptors: TraceDescriptors::empty(),
         }
     }
 
+    /// Create a quick trace from its integer values. The precise values are taken from them as they are.
+    ///
     pub fn new(id: String, trace: Vector2<i64>, average: Vector2<i64>) -> QuickTrace {
         QuickTrace {
             id,
             trace,
             average,
+            precise_trace: Vector2::new(trace.x as f64, trace.y as f64),
+            precise_average: Vector2::new(average.x as f64, average.y as f64),
+            descriptors: TraceDescriptors::empty(),
+        }
+    }
+
+    /// Get the quick access values of a full trace
+    ///
+    pub fn from_trace(id: String, trace: &Trace) -> QuickTrace {
+        QuickTrace {
+            id,
+            trace: trace.trace,
+            average: trace.average_offset,
+            precise_trace: trace.precise_trace,
+            precise_average: trace.precise_offset,
+            descriptors: trace.descriptors.clone(),
         }
     }
 }
@@ -76,113 +113,92 @@ impl LivingDataUnit {
         }
     }
 
-    pub fn load_from_file(&mut self, quick_target: String, heavy_target: String, resolution: i64) -> bool {
-        self.definitions = Vec::new();
-        self.trace_groups = Vec::new();
-
-        fs::write(String::from("Pinging file"), String::from("Ping"));
+    /// Build a data unit straight from a list of definitions, generating the quick access groups the same
+    /// way [dump_to_file] lays them out, so it can be searched without going through the files.
+    ///
+    pub fn from_definitions(definitions: Vec<DefinitionUnit>) -> LivingDataUnit {
+        let mut new_instance = LivingDataUnit {
+            definitions,
+            trace_groups: Vec::new(),
+        };
 
-        let heavy_target_content = fs::read_to_string(heavy_target);
-        let quick_target_content = fs::read_to_string(quick_target);
+        new_instance.rebuild_trace_groups();
+        new_instance
+    }
 
-        for content in heavy_target_content.expect("Cannot open content").lines() {
-            let mut new_definition_unit = DefinitionUnit::new(resolution);
+    /// Regenerate the quick access groups from the current definitions. Group N holds the Nth trace of every
+    /// definition that has at least N + 1 traces.
+    ///
+    pub fn rebuild_trace_groups(&mut self) {
+        self.trace_groups = Vec::new();
 
-            let split_content: Vec<&str> = content.split(".").collect();
-            new_definition_unit.id = (&split_content[0]).to_string();
-            let mut time_stamp = 0;
-            for split_traces in split_content[1].split(";") {
-                let split_values: Vec<&str> = split_traces.split(",").collect();
-                let mut trace_values: Vec<i64> = Vec::new();
-                for entry in split_values {
-                    match entry.parse::<i64>() {
-                        Ok(value) => trace_values.push(value),
-                        Err(_) => (),
-                    }
-                }
-                if trace_values.len() > 0 {
-                    new_definition_unit.feed(time_stamp, trace_values);
+        for definition in &self.definitions {
+            for (trace_index, trace) in definition.traces.iter().enumerate() {
+                if self.trace_groups.len() <= trace_index {
+                    self.trace_groups.push(TraceGroup::empty());
                 }
 
-                time_stamp += 1;
+                let quick_trace = QuickTrace::from_trace(definition.id.clone(), trace);
+                self.trace_groups[trace_index].group_content.push(quick_trace);
             }
-
-            self.definitions.push(new_definition_unit);
         }
+    }
 
-        let empty_line = String::from("");
-
-        let mut current_index: usize = 0;
-        let mut last_index: usize = 0;
-        let mut even_entry: bool = true;
-
-        for content in quick_target_content.expect("No QuickDB found or maybe it was empty").lines() {
-            let mut new_trace_group = TraceGroup::empty();
+    pub fn load_from_file(&mut self, quick_target: String, heavy_target: String, resolution: i64) -> bool {
+        let heavy_reader = File::open(heavy_target).expect("Cannot open content");
+        let quick_reader = File::open(quick_target).expect("No QuickDB found or maybe it was empty");
 
-            let collected_entries: Vec<&str> = content.split(".").collect();
-            let mut selected_entry = collected_entries[0];
+        self.read_from(quick_reader, heavy_reader, resolution).expect("Cannot read content")
+    }
 
-            current_index = 0;
-            even_entry = true;
-            last_index = collected_entries.len() - 1;
-            let mut new_quick_trace: QuickTrace = QuickTrace::empty();
+    /// See [LivingDataUnit::load_from_data]. Read both databases from any readers, such as files, byte slices
+    /// embedded on the binary or the entries of an archive.
+    ///
+    pub fn read_from<Q: Read, H: Read>(&mut self, mut quick_reader: Q, mut heavy_reader: H, resolution: i64) -> io::Result<bool> {
+        let mut quick_target_content = String::new();
+        let mut heavy_target_content = String::new();
+        heavy_reader.read_to_string(&mut heavy_target_content)?;
+        quick_reader.read_to_string(&mut quick_target_content)?;
 
-            while current_index < last_index {
-                selected_entry = collected_entries[current_index];
-                if even_entry {
-                    new_quick_trace = QuickTrace::empty();
-                    new_quick_trace.id = String::from(selected_entry);
-                }
-                else {
-                    let split_traces = selected_entry.split(",");
-                    let split_values: Vec<&str> = split_traces.collect();
-                    let mut index_values: Vec<i64> = Vec::new();
-                    for entry in split_values {
-                        match entry.parse::<i64>() {
-                            Ok(value) => index_values.push(value),
-                            Err(_) => (),
-                        }
-                    }
-                    new_quick_trace.trace = Vector2::new(index_values[0], index_values[1]);
-                    new_quick_trace.average = Vector2::new(index_values[2], index_values[3]);
-                    new_trace_group.group_content.push(new_quick_trace.clone());
-                }
+        self.definitions = Vec::new();
+        self.trace_groups = Vec::new();
 
-                current_index += 1;
-                even_entry = current_index % 2 == 0;
-            }
+        self.read_data(quick_target_content, heavy_target_content, resolution)
+    }
 
-            self.trace_groups.push(new_trace_group);
-        }
+    /// See [LivingDataUnit::read_from]. Read both databases from files with exactly the given paths.
+    ///
+    pub fn load<Q: AsRef<Path>, H: AsRef<Path>>(&mut self, quick_path: Q, heavy_path: H, resolution: i64) -> io::Result<bool> {
+        self.read_from(File::open(quick_path)?, File::open(heavy_path)?, resolution)
+    }
 
-        for definition_found in &self.definitions {
-            let trace_length = definition_found.traces.len();
-            let mut current_index = 0;
-            while current_index < trace_length {
-                let heavy_trace = &definition_found.traces[current_index];
-                for quick_trace in &self.trace_groups[current_index].group_content {
-                    if quick_trace.id == definition_found.id {
-                        if !quick_trace.trace.equals(&heavy_trace.trace) {
-                            return false;
-                        }
-                        if !quick_trace.average.equals(&heavy_trace.average_offset) {
-                            return false;
-                        }
-                    }
-                }
-                current_index += 1;
-            }
+    /// See [load_from_file]. Read the database from its contents directly. Quick access entries written before
+    /// the trace descriptors or the precise values were stored take them from the heavy access definitions.
+    ///
+    /// # Panics
+    ///
+    /// The contents have to be laid out as written by [LivingDataUnit::write_to], use [LivingDataUnit::read_from]
+    /// to get an error instead
+    ///
+    pub fn load_from_data(&mut self, quick_target_content: String, heavy_target_content: String, resolution: i64) -> bool {
+        match self.read_data(quick_target_content, heavy_target_content, resolution) {
+            Ok(consistent) => consistent,
+            Err(error) => panic!("Database content couldn't be read: {}", error),
         }
-
-        return true;
     }
 
-    pub fn load_from_data(&mut self, quick_target_content: String, heavy_target_content: String, resolution: i64) -> bool {
+    /// See [LivingDataUnit::load_from_data]. Lines that don't hold the values expected are invalid data.
+    ///
+    fn read_data(&mut self, quick_target_content: String, heavy_target_content: String, resolution: i64) -> io::Result<bool> {
 
         for content in heavy_target_content.lines() {
             let mut new_definition_unit = DefinitionUnit::new(resolution);
 
             let split_content: Vec<&str> = content.split(".").collect();
+            if split_content.len() < 2 {
+                return Err(io::Error::new(io::ErrorKind::InvalidData,
+                    format!("Heavy access line \"{}\" has no traces after its id", content)));
+            }
             new_definition_unit.id = (&split_content[0]).to_string();
             let mut time_stamp = 0;
             for split_traces in split_content[1].split(";") {
@@ -204,12 +220,14 @@ impl LivingDataUnit {
             self.definitions.push(new_definition_unit);
         }
 
-        let empty_line = String::from("");
-
         let mut current_index: usize = 0;
         let mut last_index: usize = 0;
         let mut even_entry: bool = true;
 
+        let group_offset = self.trace_groups.len();
+        let mut missing_descriptors: Vec<(usize, usize)> = Vec::new();
+        let mut missing_precise: Vec<(usize, usize)> = Vec::new();
+
         for content in quick_target_content.lines() {
             let mut new_trace_group = TraceGroup::empty();
 
@@ -228,17 +246,38 @@ impl LivingDataUnit {
                     new_quick_trace.id = String::from(selected_entry);
                 }
                 else {
-                    let split_traces = selected_entry.split(",");
-                    let split_values: Vec<&str> = split_traces.collect();
+                    let split_values: Vec<&str> = selected_entry.split(",").filter(|entry| !entry.is_empty()).collect();
                     let mut index_values: Vec<i64> = Vec::new();
-                    for entry in split_values {
+                    for entry in split_values.iter().take(4) {
                         match entry.parse::<i64>() {
                             Ok(value) => index_values.push(value),
                             Err(_) => (),
                         }
                     }
+                    if index_values.len() < 4 {
+                        return Err(io::Error::new(io::ErrorKind::InvalidData,
+                            format!("Quick access entry \"{}\" needs a trace and an average", selected_entry)));
+                    }
                     new_quick_trace.trace = Vector2::new(index_values[0], index_values[1]);
                     new_quick_trace.average = Vector2::new(index_values[2], index_values[3]);
+
+                    match TraceDescriptors::from_values(&split_values[4..]) {
+                        Some(descriptors) => new_quick_trace.descriptors = descriptors,
+                        None => missing_descriptors.push((self.trace_groups.len(), new_trace_group.group_content.len())),
+                    }
+
+                    let precise_start = 4 + DESCRIPTOR_VALUES;
+                    let precise_values: Vec<f64> = split_values.iter().skip(precise_start).take(PRECISE_VALUES)
+                        .filter_map(|entry| read_stored_decimal(entry))
+                        .collect();
+                    if precise_values.len() == PRECISE_VALUES {
+                        new_quick_trace.precise_trace = Vector2::new(precise_values[0], precise_values[1]);
+                        new_quick_trace.precise_average = Vector2::new(precise_values[2], precise_values[3]);
+                    }
+                    else {
+                        missing_precise.push((self.trace_groups.len(), new_trace_group.group_content.len()));
+                    }
+
                     new_trace_group.group_content.push(new_quick_trace.clone());
                 }
 
@@ -249,18 +288,47 @@ impl LivingDataUnit {
             self.trace_groups.push(new_trace_group);
         }
 
+        for (group_index, content_index) in missing_descriptors {
+            let quick_trace = &self.trace_groups[group_index].group_content[content_index];
+            if let Some(heavy_trace) = self.find_trace(&quick_trace.id, group_index - group_offset) {
+                let descriptors = heavy_trace.descriptors.clone();
+                self.trace_groups[group_index].group_content[content_index].descriptors = descriptors;
+            }
+        }
+
+        for (group_index, content_index) in missing_precise {
+            let quick_trace = &self.trace_groups[group_index].group_content[content_index];
+            if let Some(heavy_trace) = self.find_trace(&quick_trace.id, group_index - group_offset) {
+                let (precise_trace, precise_offset) = (heavy_trace.precise_trace, heavy_trace.precise_offset);
+                let quick_trace = &mut self.trace_groups[group_index].group_content[content_index];
+                quick_trace.precise_trace = precise_trace;
+                quick_trace.precise_average = precise_offset;
+            }
+        }
+
         for definition_found in &self.definitions {
             let trace_length = definition_found.traces.len();
             let mut current_index = 0;
             while current_index < trace_length {
                 let heavy_trace = &definition_found.traces[current_index];
-                for quick_trace in &self.trace_groups[current_index].group_content {
+                let trace_group = match self.trace_groups.get(current_index) {
+                    Some(trace_group) => trace_group,
+                    None => return Ok(false),
+                };
+                for quick_trace in &trace_group.group_content {
                     if quick_trace.id == definition_found.id {
                         if !quick_trace.trace.equals(&heavy_trace.trace) {
-                            return false;
+                            return Ok(false);
                         }
                         if !quick_trace.average.equals(&heavy_trace.average_offset) {
-                            return false;
+                            return Ok(false);
+                        }
+                        if !quick_trace.descriptors.equals(&heavy_trace.descriptors) {
+                            return Ok(false);
+                        }
+                        if !precise_equals(&quick_trace.precise_trace, &heavy_trace.precise_trace) ||
+                           !precise_equals(&quick_trace.precise_average, &heavy_trace.precise_offset) {
+                            return Ok(false);
                         }
                     }
                 }
@@ -268,13 +336,44 @@ impl LivingDataUnit {
             }
         }
 
-        return true;
+        Ok(true)
     }
 
-    pub fn dump_to_file(&self, append_name: String) {
+    /// Get the trace of a definition by its id and the position of the trace, if there is any
+    ///
+    fn find_trace(&self, id: &str, trace_index: usize) -> Option<&Trace> {
+        self.definitions.iter()
+            .find(|definition| definition.id == id && trace_index < definition.traces.len())
+            .map(|definition| &definition.traces[trace_index])
+    }
+
+    /// See [LivingDataUnit::save]. Write both databases on the working directory, as "quickaccess_" and
+    /// "heavyaccess_" followed by the given name.
+    ///
+    pub fn dump_to_file(&self, append_name: String) -> io::Result<()> {
         let quick_name = String::from("quickaccess_") + &append_name;
         let heavy_name = String::from("heavyaccess_") + &append_name;
 
+        self.save(quick_name, heavy_name)
+    }
+
+    /// Write the quick access and the heavy access databases to any writers, such as files or sockets. Read them
+    /// back with [LivingDataUnit::read_from].
+    ///
+    pub fn write_to<Q: Write, H: Write>(&self, quick_writer: &mut Q, heavy_writer: &mut H) -> io::Result<()> {
+        quick_writer.write_all(self.quick_access_content().as_bytes())?;
+        heavy_writer.write_all(self.heavy_access_content().as_bytes())
+    }
+
+    /// See [LivingDataUnit::write_to]. Write both databases to files with exactly the given paths.
+    ///
+    pub fn save<Q: AsRef<Path>, H: AsRef<Path>>(&self, quick_path: Q, heavy_path: H) -> io::Result<()> {
+        self.write_to(&mut File::create(quick_path)?, &mut File::create(heavy_path)?)
+    }
+
+    /// Quick access database, with a line for every trace position holding that trace of every definition
+    ///
+    fn quick_access_content(&self) -> String {
         let mut quick_output = String::from("");
         let mut formatted_trace = String::from("");
         let comma_str = String::from(",");
@@ -306,6 +405,11 @@ impl LivingDataUnit {
                                     +   &selected_trace.trace.y.to_string() + &(comma_str)
                                     +   &selected_trace.average_offset.x.to_string() + &(comma_str)
                                     +   &selected_trace.average_offset.y.to_string() + &(comma_str)
+                                    +   &selected_trace.descriptors.to_values()
+                                    +   &stored_decimal(selected_trace.precise_trace.x) + &(comma_str)
+                                    +   &stored_decimal(selected_trace.precise_trace.y) + &(comma_str)
+                                    +   &stored_decimal(selected_trace.precise_offset.x) + &(comma_str)
+                                    +   &stored_decimal(selected_trace.precise_offset.y) + &(comma_str)
                                     +   &(String::from(".")));
                     quick_output += &formatted_trace;
                 }
@@ -316,15 +420,24 @@ impl LivingDataUnit {
             }
         }
 
-        fs::write(&quick_name, quick_output);
+        quick_output
+    }
 
+    /// Heavy access database, with a line for every definition holding the indexes of all its traces
+    ///
+    fn heavy_access_content(&self) -> String {
         let mut heavy_output = String::from("");
+        let mut formatted_trace: String;
+        let comma_str = String::from(",");
+
+        let definitions_size = self.definitions.len();
+        let mut selected_definition: &DefinitionUnit;
         let mut last_item: usize = 0;
 
         let mut trace_index: usize = 0;
         let mut trace_size: usize = 0;
  
-        current_check = 0;
+        let mut current_check = 0;
         while current_check < definitions_size {
             selected_definition = &self.definitions[current_check];
                     
@@ -350,6 +463,25 @@ impl LivingDataUnit {
             current_check += 1;
         }
 
-        fs::write(&heavy_name, heavy_output);
+        heavy_output
     }
-}
\ No newline at end of file
+}
+
+/// Write a decimal value as the scaled integer the quick access database keeps, see [STORED_DECIMALS_FACTOR]
+///
+fn stored_decimal(value: f64) -> String {
+    ((value * STORED_DECIMALS_FACTOR).round() as i64).to_string()
+}
+
+/// See [stored_decimal]. Read the decimal value back.
+///
+fn read_stored_decimal(value: &str) -> Option<f64> {
+    value.parse::<i64>().ok().map(|stored| stored as f64 / STORED_DECIMALS_FACTOR)
+}
+
+/// Check if two precise vectors are the same, allowing the margin lost when they are stored
+///
+fn precise_equals(first: &Vector2<f64>, second: &Vector2<f64>) -> bool {
+    close_enough_f64(first.x, second.x, 1.0 / STORED_DECIMALS_FACTOR) &&
+    close_enough_f64(first.y, second.y, 1.0 / STORED_DECIMALS_FACTOR)
+}
@@ -1,21 +1,255 @@
 use std::fmt;
 use std::collections::HashSet;
 
-use std::fs;
 
 use crate::Vector2;
+use crate::DebugSink;
+use crate::NullSink;
 
 use crate::get_coordinates_from;
 use crate::sum_i64_vectors;
 use crate::sub_vectors;
 use crate::scale_vector;
 use crate::cos_between;
+use crate::sum_f64_vectors;
+use crate::sub_f64_vectors;
+use crate::scale_f64_vector;
 use crate::close_enough_f64;
+use crate::close_enough;
+use crate::get_index_distance;
 
 const ERROR_FACTOR: f64 = 0.2;
 const COS_ERROR: f64 = 0.86;
 const COS_REST: f64 = 0.14;
 
+/// Number of bins the turning angles of a trace are split into, covering (-pi, pi]
+///
+pub const TURNING_BINS: usize = 8;
+/// Number of values a [TraceDescriptors] takes when written to the quick access database
+///
+pub const DESCRIPTOR_VALUES: usize = 8 + TURNING_BINS;
+/// Decimal values can't be written with a dot on the quick access database, as it separates its entries, so they
+/// are stored as integers scaled by this factor
+///
+pub const STORED_DECIMALS_FACTOR: f64 = 1000.0;
+
+/// Shape of a trace beyond its displacement and average offset. Two strokes that start and end on the same
+/// points, like a hook and a straight line, can be told apart through these.
+///
+#[derive(Clone)]
+pub struct TraceDescriptors {
+    /// Sum of the distances between consecutive indexes
+    ///
+    pub path_length: f64,
+    /// Top left corner of the box containing the trace, as coordinates
+    ///
+    pub bounding_min: Vector2<i64>,
+    /// Bottom right corner of the box containing the trace, as coordinates
+    ///
+    pub bounding_max: Vector2<i64>,
+
+    /// Quadrant of the grid where the trace starts: 0 top left, 1 top right, 2 bottom left, 3 bottom right
+    ///
+    pub start_quadrant: u8,
+    /// See [start_quadrant]. Quadrant where the trace ends.
+    ///
+    pub end_quadrant: u8,
+
+    /// How many times the trace turns by each angle range, from -pi (the stroke comes back clockwise) to
+    /// pi (it comes back counterclockwise), with straight steps falling on the middle bin
+    ///
+    pub turning_histogram: [u32; TURNING_BINS],
+    /// How many times the trace changes from bending one way to bending the other
+    ///
+    pub curvature_sign_changes: u32,
+}
+
+impl TraceDescriptors {
+    pub fn new(indexes: &[i64], resolution: i64) -> TraceDescriptors {
+        let mut descriptors = TraceDescriptors::empty();
+        if indexes.is_empty() || resolution <= 0 {
+            return descriptors;
+        }
+
+        let coordinates: Vec<Vector2<i64>> = indexes.iter().map(|index| get_coordinates_from(*index, resolution)).collect();
+
+        descriptors.bounding_min = coordinates[0];
+        descriptors.bounding_max = coordinates[0];
+        for point in &coordinates {
+            descriptors.bounding_min = Vector2::new(descriptors.bounding_min.x.min(point.x), descriptors.bounding_min.y.min(point.y));
+            descriptors.bounding_max = Vector2::new(descriptors.bounding_max.x.max(point.x), descriptors.bounding_max.y.max(point.y));
+        }
+
+        descriptors.start_quadrant = quadrant_of(&coordinates[0], resolution);
+        descriptors.end_quadrant = quadrant_of(&coordinates[coordinates.len() - 1], resolution);
+
+        let mut steps: Vec<Vector2<i64>> = Vec::new();
+        for pair in coordinates.windows(2) {
+            let step = sub_vectors(&pair[1], &pair[0]);
+            if step.x == 0 && step.y == 0 {
+                continue;
+            }
+
+            descriptors.path_length += ((step.x * step.x + step.y * step.y) as f64).sqrt();
+            steps.push(step);
+        }
+
+        let bin_width = 2.0 * std::f64::consts::PI / TURNING_BINS as f64;
+        let mut last_sign: i64 = 0;
+        for pair in steps.windows(2) {
+            let cross = pair[0].x * pair[1].y - pair[0].y * pair[1].x;
+            let dot = pair[0].x * pair[1].x + pair[0].y * pair[1].y;
+            let angle = (cross as f64).atan2(dot as f64);
+
+            let bin = ((angle + std::f64::consts::PI) / bin_width).floor() as usize;
+            descriptors.turning_histogram[bin.min(TURNING_BINS - 1)] += 1;
+
+            let sign = cross.signum();
+            if sign != 0 {
+                if last_sign != 0 && sign != last_sign {
+                    descriptors.curvature_sign_changes += 1;
+                }
+                last_sign = sign;
+            }
+        }
+
+        descriptors
+    }
+
+    pub fn empty() -> TraceDescriptors {
+        TraceDescriptors {
+            path_length: 0.0,
+            bounding_min: Vector2::new(0, 0),
+            bounding_max: Vector2::new(0, 0),
+            start_quadrant: 0,
+            end_quadrant: 0,
+            turning_histogram: [0; TURNING_BINS],
+            curvature_sign_changes: 0,
+        }
+    }
+
+    /// Get how alike two sets of descriptors are, from 0 (nothing in common) to 1 (same shape). Every
+    /// descriptor weights the same on the result.
+    ///
+    pub fn likeness(&self, other: &TraceDescriptors) -> f64 {
+        let length_likeness = ratio_likeness(self.path_length, other.path_length);
+
+        let width_likeness = ratio_likeness((self.bounding_max.x - self.bounding_min.x + 1) as f64,
+                                            (other.bounding_max.x - other.bounding_min.x + 1) as f64);
+        let height_likeness = ratio_likeness((self.bounding_max.y - self.bounding_min.y + 1) as f64,
+                                             (other.bounding_max.y - other.bounding_min.y + 1) as f64);
+        let box_likeness = (width_likeness + height_likeness) / 2.0;
+
+        let mut quadrant_likeness = 0.0;
+        if self.start_quadrant == other.start_quadrant {
+            quadrant_likeness += 0.5;
+        }
+        if self.end_quadrant == other.end_quadrant {
+            quadrant_likeness += 0.5;
+        }
+
+        let mut dot_product = 0.0;
+        let mut magnitude_1 = 0.0;
+        let mut magnitude_2 = 0.0;
+        for bin in 0..TURNING_BINS {
+            let value_1 = self.turning_histogram[bin] as f64;
+            let value_2 = other.turning_histogram[bin] as f64;
+            dot_product += value_1 * value_2;
+            magnitude_1 += value_1 * value_1;
+            magnitude_2 += value_2 * value_2;
+        }
+        let turning_likeness = if magnitude_1 == 0.0 && magnitude_2 == 0.0 {
+            1.0
+        } else if magnitude_1 == 0.0 || magnitude_2 == 0.0 {
+            0.0
+        } else {
+            dot_product / (magnitude_1.sqrt() * magnitude_2.sqrt())
+        };
+
+        let sign_difference = (self.curvature_sign_changes as f64 - other.curvature_sign_changes as f64).abs();
+        let curvature_likeness = 1.0 / (1.0 + sign_difference);
+
+        (length_likeness + box_likeness + quadrant_likeness + turning_likeness + curvature_likeness) / 5.0
+    }
+
+    /// Write the descriptors as the comma separated values used by the quick access database
+    ///
+    pub fn to_values(&self) -> String {
+        let mut values: Vec<String> = vec![
+            ((self.path_length * STORED_DECIMALS_FACTOR).round() as i64).to_string(),
+            self.bounding_min.x.to_string(),
+            self.bounding_min.y.to_string(),
+            self.bounding_max.x.to_string(),
+            self.bounding_max.y.to_string(),
+            self.start_quadrant.to_string(),
+            self.end_quadrant.to_string(),
+        ];
+        for bin in &self.turning_histogram {
+            values.push(bin.to_string());
+        }
+        values.push(self.curvature_sign_changes.to_string());
+
+        values.join(",") + ","
+    }
+
+    /// Read the descriptors back from the values written by [to_values]. Returns None if there are not
+    /// enough values or any of them cannot be read.
+    ///
+    pub fn from_values(values: &[&str]) -> Option<TraceDescriptors> {
+        if values.len() < DESCRIPTOR_VALUES {
+            return None;
+        }
+
+        let mut descriptors = TraceDescriptors::empty();
+        descriptors.path_length = values[0].parse::<i64>().ok()? as f64 / STORED_DECIMALS_FACTOR;
+        descriptors.bounding_min = Vector2::new(values[1].parse::<i64>().ok()?, values[2].parse::<i64>().ok()?);
+        descriptors.bounding_max = Vector2::new(values[3].parse::<i64>().ok()?, values[4].parse::<i64>().ok()?);
+        descriptors.start_quadrant = values[5].parse::<u8>().ok()?;
+        descriptors.end_quadrant = values[6].parse::<u8>().ok()?;
+        for bin in 0..TURNING_BINS {
+            descriptors.turning_histogram[bin] = values[7 + bin].parse::<u32>().ok()?;
+        }
+        descriptors.curvature_sign_changes = values[7 + TURNING_BINS].parse::<u32>().ok()?;
+
+        Some(descriptors)
+    }
+
+    /// Check if the descriptors have the same content, allowing on the path length the margin lost when it is
+    /// stored
+    ///
+    pub fn equals(&self, other: &TraceDescriptors) -> bool {
+        close_enough_f64(self.path_length, other.path_length, 1.0 / STORED_DECIMALS_FACTOR) &&
+        self.bounding_min.equals(&other.bounding_min) &&
+        self.bounding_max.equals(&other.bounding_max) &&
+        self.start_quadrant == other.start_quadrant &&
+        self.end_quadrant == other.end_quadrant &&
+        self.turning_histogram == other.turning_histogram &&
+        self.curvature_sign_changes == other.curvature_sign_changes
+    }
+}
+
+/// See [TraceDescriptors]. The central row or column, on odd resolutions, is counted on the bottom or right side.
+///
+fn quadrant_of(point: &Vector2<i64>, resolution: i64) -> u8 {
+    let mut quadrant = 0;
+    if point.x * 2 >= resolution {
+        quadrant += 1;
+    }
+    if point.y * 2 >= resolution {
+        quadrant += 2;
+    }
+
+    quadrant
+}
+
+fn ratio_likeness(value_1: f64, value_2: f64) -> f64 {
+    if value_1 <= 0.0 && value_2 <= 0.0 {
+        return 1.0;
+    }
+
+    value_1.min(value_2) / value_1.max(value_2)
+}
+
 #[derive(Clone)]
 pub struct Trace {
     pub time_stamp: i64,
@@ -25,6 +259,16 @@ pub struct Trace {
     pub trace: Vector2<i64>,
     pub average_offset: Vector2<i64>,
 
+    /// See [trace]. Same displacement, kept as decimals so it can be compared with [precise_offset].
+    ///
+    pub precise_trace: Vector2<f64>,
+    /// See [average_offset]. The average of the coordinates is not floored, so the offset keeps the decimals
+    /// that get lost on small resolutions.
+    ///
+    pub precise_offset: Vector2<f64>,
+
+    pub descriptors: TraceDescriptors,
+
     pub resolution: i64,
 }
 
@@ -39,10 +283,12 @@ impl Trace {
         let trace_value = sub_vectors(&last_displacement, &first_displacement);
         
         let mut total_sum: Vector2<i64> = Vector2::new(0, 0);
+        let mut precise_sum: Vector2<f64> = Vector2::new(0.0, 0.0);
         let mut element_count = 0;
         for entry in &indexes {
             let mut result = get_coordinates_from(*entry, resolution);
             total_sum = sum_i64_vectors(&total_sum, &result);
+            precise_sum = sum_f64_vectors(&precise_sum, &Vector2::new(result.x as f64, result.y as f64));
 
             element_count += 1;
         }
@@ -50,6 +296,12 @@ impl Trace {
         total_sum = scale_vector(&total_sum, element_count);
         let aoffset_value = sub_vectors(&total_sum, &first_displacement);
 
+        let precise_first = Vector2::new(first_displacement.x as f64, first_displacement.y as f64);
+        precise_sum = scale_f64_vector(&precise_sum, element_count as f64);
+        let precise_offset = sub_f64_vectors(&precise_sum, &precise_first);
+
+        let descriptors = TraceDescriptors::new(&indexes, resolution);
+
         let mut new_trace = Trace {
             time_stamp,
             indexes,
@@ -57,6 +309,11 @@ impl Trace {
             trace: trace_value,
             average_offset: aoffset_value,
 
+            precise_trace: Vector2::new(trace_value.x as f64, trace_value.y as f64),
+            precise_offset,
+
+            descriptors,
+
             resolution,
         };
 
@@ -69,6 +326,9 @@ impl Trace {
             indexes: Vec::new(),
             trace: Vector2::new(0, 0),
             average_offset: Vector2::new(0, 0),
+            precise_trace: Vector2::new(0.0, 0.0),
+            precise_offset: Vector2::new(0.0, 0.0),
+            descriptors: TraceDescriptors::empty(),
             resolution: 0,
         }
     }
@@ -209,7 +469,16 @@ impl TrainingUnit {
         self.training_instances.push(new_instance);
     }
 
+    /// Train the base definition with every instance fed so far. The report of the training is dropped, see
+    /// [TrainingUnit::train_w_report_on] to keep it.
+    ///
     pub fn train_w_report(&mut self) -> DefinitionUnit {
+        self.train_w_report_on(&NullSink)
+    }
+
+    /// See [TrainingUnit::train_w_report]. Hand the report to the sink as "debug_report_data.txt".
+    ///
+    pub fn train_w_report_on(&mut self, sink: &dyn DebugSink) -> DefinitionUnit {
         if (self.training_instances.len() == 0) {
             panic!("There are no definition units for training. Cancelled.");
         }
@@ -315,7 +584,7 @@ impl TrainingUnit {
         report += &(String::from(".............") + &new_line);
         report += &(String::from("Remember: Objects are not overwriten anywhere. Hang on the output. "));
 
-        fs::write(String::from("debug_report_data.txt"), report);
+        sink.write("debug_report_data.txt", &report);
 
         return new_definition;
     }
@@ -384,21 +653,54 @@ impl TrainingUnit {
             new_rearguard = current_guard;
         }
 
-        return Self::combine_into_trace(new_content, new_vanguard, new_rearguard, &base_trace.time_stamp, &base_trace.resolution);
+        return Self::combine_into_trace(new_content, new_vanguard, new_rearguard, base_trace);
     }
 
-    fn combine_into_trace(content: Vec<i64>, vanguard: i64, rearguard: i64, time_stamp: &i64, resolution: &i64) -> Trace{
-        let mut new_trace_data = vec![vanguard.clone()];
+    /// Rebuild a trace from the voted cells as a path that starts at the vanguard and ends at the rearguard.
+    /// The cells in between are walked by always moving to the closest cell not yet visited, so a stroke
+    /// that bends back over itself keeps its drawing order.
+    ///
+    /// # Ties
+    ///
+    /// When several cells are at the same distance, the one that appears first on the base trace wins, as it
+    /// is the order that was already learnt. Cells the base trace never had are taken last, by index.
+    ///
+    fn combine_into_trace(content: Vec<i64>, vanguard: i64, rearguard: i64, base_trace: &Trace) -> Trace {
+        let resolution = base_trace.resolution;
+
+        let mut pending: Vec<i64> = Vec::new();
         for entry in content {
-            if !new_trace_data.contains(&entry) && entry != rearguard {
-                new_trace_data.push(entry);
+            if entry != vanguard && entry != rearguard && !pending.contains(&entry) {
+                pending.push(entry);
             }
         }
-        new_trace_data.push(rearguard);
 
-        
+        let mut new_trace_data = vec![vanguard];
+        let mut current_index = vanguard;
+        while !pending.is_empty() {
+            let mut best_position: usize = 0;
+            let mut best_distance = f32::MAX;
+            let mut best_order = usize::MAX;
+
+            for (position, entry) in pending.iter().enumerate() {
+                let distance = get_index_distance(current_index as i32, *entry as i32, resolution as usize);
+                let order = base_trace.indexes.iter().position(|index| index == entry).unwrap_or(usize::MAX);
+
+                let closer = distance < best_distance && !close_enough(distance, best_distance, 0.001);
+                let same_distance = close_enough(distance, best_distance, 0.001);
+                if closer || (same_distance && order < best_order) {
+                    best_position = position;
+                    best_distance = distance;
+                    best_order = order;
+                }
+            }
+
+            current_index = pending.remove(best_position);
+            new_trace_data.push(current_index);
+        }
+        new_trace_data.push(rearguard);
 
-        Trace::new(*time_stamp, new_trace_data, *resolution)
+        Trace::new(base_trace.time_stamp, new_trace_data, resolution)
     }
 
     pub fn report_compatibility(base_unit: &DefinitionUnit, entry_unit: &DefinitionUnit, error_margin: f64) -> CompatibilityReport {
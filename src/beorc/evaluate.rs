@@ -0,0 +1,286 @@
+use std::fmt;
+
+use crate::DefinitionUnit;
+use crate::TrainingUnit;
+use crate::LivingDataUnit;
+use crate::Medium;
+
+/// Results of running a labelled set of definitions through [Medium]. Every sample is counted once, using
+/// its id as the expected label.
+///
+pub struct EvaluationReport {
+    /// Every label that can appear on the confusion matrix, in order of appearance
+    ///
+    pub ids: Vec<String>,
+    /// Rows are the expected label and columns the best prediction, both following the order in ids. The last
+    /// column counts the samples for which the medium had no prediction at all.
+    ///
+    pub confusion: Vec<Vec<usize>>,
+
+    /// How many predictions are accepted for the top-k hits
+    ///
+    pub top_k: usize,
+    pub total: usize,
+    pub top1_hits: usize,
+    pub topk_hits: usize,
+}
+
+impl EvaluationReport {
+    pub fn new(ids: Vec<String>, top_k: usize) -> EvaluationReport {
+        let label_count = ids.len();
+
+        EvaluationReport {
+            ids,
+            confusion: vec![vec![0; label_count + 1]; label_count],
+
+            top_k,
+            total: 0,
+            top1_hits: 0,
+            topk_hits: 0,
+        }
+    }
+
+    /// Count a sample with its expected label and the ranking of ids given by the medium, best first
+    ///
+    /// # Panics
+    ///
+    /// The expected label has to be one of the ids the report was created with
+    ///
+    pub fn record(&mut self, expected: &str, ranking: &[String]) {
+        let expected_position = match self.label_position(expected) {
+            Some(position) => position,
+            None => panic!("The label {} is not known by this report", expected),
+        };
+
+        let predicted_position = match ranking.first() {
+            Some(best) => self.label_position(best).unwrap_or(self.ids.len()),
+            None => self.ids.len(),
+        };
+
+        self.confusion[expected_position][predicted_position] += 1;
+        self.total += 1;
+
+        if predicted_position == expected_position {
+            self.top1_hits += 1;
+        }
+        if ranking.iter().take(self.top_k).any(|id| id == expected) {
+            self.topk_hits += 1;
+        }
+    }
+
+    /// Add the counts of another report, see [k_fold_evaluation]. Labels are matched by id, so the other report
+    /// can list them in any order. As on [EvaluationReport::record], predictions of labels this report doesn't
+    /// know go with the samples that had no prediction.
+    ///
+    /// # Panics
+    ///
+    /// Every label of the other report with samples has to be one of the ids this report was created with
+    ///
+    pub fn merge(&mut self, other: &EvaluationReport) {
+        for (other_expected, other_row) in other.confusion.iter().enumerate() {
+            if other_row.iter().all(|times| *times == 0) {
+                continue;
+            }
+
+            let expected_position = match self.label_position(&other.ids[other_expected]) {
+                Some(position) => position,
+                None => panic!("The label {} is not known by this report", other.ids[other_expected]),
+            };
+
+            for (other_predicted, times) in other_row.iter().enumerate() {
+                let predicted_position = match other.ids.get(other_predicted) {
+                    Some(predicted) => self.label_position(predicted).unwrap_or(self.ids.len()),
+                    None => self.ids.len(),
+                };
+                self.confusion[expected_position][predicted_position] += times;
+            }
+        }
+
+        self.total += other.total;
+        self.top1_hits += other.top1_hits;
+        self.topk_hits += other.topk_hits;
+    }
+
+    pub fn top1_accuracy(&self) -> f64 {
+        if self.total == 0 {
+            return 0.0;
+        }
+
+        self.top1_hits as f64 / self.total as f64
+    }
+
+    pub fn topk_accuracy(&self) -> f64 {
+        if self.total == 0 {
+            return 0.0;
+        }
+
+        self.topk_hits as f64 / self.total as f64
+    }
+
+    /// Get the pairs (expected, predicted, times) that were mistaken the most, sorted from the most common
+    /// confusion. Samples without a prediction are not listed.
+    ///
+    pub fn most_confused(&self, count: usize) -> Vec<(String, String, usize)> {
+        let mut result: Vec<(String, String, usize)> = Vec::new();
+
+        for (expected, row) in self.confusion.iter().enumerate() {
+            for (predicted, times) in row.iter().enumerate().take(self.ids.len()) {
+                if expected != predicted && *times > 0 {
+                    result.push((self.ids[expected].clone(), self.ids[predicted].clone(), *times));
+                }
+            }
+        }
+
+        result.sort_by_key(|entry| std::cmp::Reverse(entry.2));
+        result.truncate(count);
+        result
+    }
+
+    fn label_position(&self, id: &str) -> Option<usize> {
+        self.ids.iter().position(|label| label == id)
+    }
+}
+
+impl fmt::Display for EvaluationReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut result_string = String::from("");
+        let new_line = String::from("\n");
+
+        result_string += &(String::from("Samples: ") + &self.total.to_string() + &new_line);
+        result_string += &(String::from("Top-1 accuracy: ") + &self.top1_accuracy().to_string() + &new_line);
+        result_string += &(String::from("Top-") + &self.top_k.to_string() + &String::from(" accuracy: ")
+                        + &self.topk_accuracy().to_string() + &new_line);
+
+        result_string += &(String::from("Confusion (expected / predicted): ") + &new_line);
+        for (expected, row) in self.confusion.iter().enumerate() {
+            result_string += &(format!("{:>12}", self.ids[expected]));
+            for times in row {
+                result_string += &(format!("{:>5}", times));
+            }
+            result_string += &new_line;
+        }
+
+        for (expected, predicted, times) in self.most_confused(5) {
+            result_string += &(expected + &String::from(" taken as ") + &predicted + &String::from(": ")
+                            + &times.to_string() + &new_line);
+        }
+
+        write!(f, "{}", result_string)
+    }
+}
+
+/// Feed every trace of the sample to the medium, in order, and get the ids it predicts from the best to the
+/// worst likeness.
+///
+pub fn rank_sample(medium: &mut Medium, sample: &DefinitionUnit) -> Vec<String> {
+    medium.reset_search();
+    for trace in &sample.traces {
+        medium.feed_trace(trace.clone());
+    }
+
+    let mut ordered: Vec<(String, f64)> = medium.predictions.iter()
+        .map(|entry| (entry.id.clone(), entry.likeness))
+        .collect();
+    ordered.sort_by(|first, second| second.1.total_cmp(&first.1));
+
+    let mut ranking: Vec<String> = Vec::new();
+    for (id, _) in ordered {
+        if !ranking.contains(&id) {
+            ranking.push(id);
+        }
+    }
+
+    ranking
+}
+
+/// Run a labelled test set through a [Medium] built on the given data unit. The id of every sample is used as
+/// its expected label.
+///
+pub fn evaluate(data_unit: &LivingDataUnit, test_set: &[DefinitionUnit], top_k: usize) -> EvaluationReport {
+    let mut ids: Vec<String> = Vec::new();
+    for definition in data_unit.definitions.iter().chain(test_set.iter()) {
+        if !ids.contains(&definition.id) {
+            ids.push(definition.id.clone());
+        }
+    }
+
+    let mut report = EvaluationReport::new(ids, top_k);
+    let mut medium = Medium::new(data_unit.clone());
+    for sample in test_set {
+        let ranking = rank_sample(&mut medium, sample);
+        report.record(&sample.id, &ranking);
+    }
+
+    report
+}
+
+/// Split the samples in k folds and, for every fold, train a definition per id with the rest of samples
+/// through a [TrainingUnit] and evaluate the fold against them. The counts of all folds are merged on a single
+/// report.
+///
+/// # Folds
+///
+/// Samples are distributed per id, so the nth sample of every id goes to fold n % folds. The first training
+/// sample of an id is used as the base of its [TrainingUnit]; if there is no other one, it is used as it is.
+///
+/// # Panics
+///
+/// There has to be at least one fold
+///
+pub fn k_fold_evaluation(samples: &[DefinitionUnit], folds: usize, top_k: usize, error_margin: f64) -> EvaluationReport {
+    if folds == 0 {
+        panic!("A k-fold evaluation needs at least one fold");
+    }
+
+    let mut ids: Vec<String> = Vec::new();
+    let mut fold_of_sample: Vec<usize> = Vec::new();
+    let mut seen_per_id: Vec<usize> = Vec::new();
+    for sample in samples {
+        let id_position = match ids.iter().position(|id| *id == sample.id) {
+            Some(position) => position,
+            None => {
+                ids.push(sample.id.clone());
+                seen_per_id.push(0);
+                ids.len() - 1
+            }
+        };
+
+        fold_of_sample.push(seen_per_id[id_position] % folds);
+        seen_per_id[id_position] += 1;
+    }
+
+    let mut report = EvaluationReport::new(ids.clone(), top_k);
+    for fold in 0..folds {
+        let mut definitions: Vec<DefinitionUnit> = Vec::new();
+        for id in &ids {
+            let training: Vec<&DefinitionUnit> = samples.iter().zip(fold_of_sample.iter())
+                .filter(|(sample, sample_fold)| sample.id == *id && **sample_fold != fold)
+                .map(|(sample, _)| sample)
+                .collect();
+
+            if training.is_empty() {
+                continue;
+            }
+            if training.len() == 1 {
+                definitions.push(training[0].clone());
+                continue;
+            }
+
+            let mut training_unit = TrainingUnit::new(training[0], error_margin);
+            for instance in &training[1..] {
+                training_unit.feed((*instance).clone());
+            }
+            definitions.push(training_unit.train_w_report());
+        }
+
+        let test_set: Vec<DefinitionUnit> = samples.iter().zip(fold_of_sample.iter())
+            .filter(|(_, sample_fold)| **sample_fold == fold)
+            .map(|(sample, _)| sample.clone())
+            .collect();
+
+        let data_unit = LivingDataUnit::from_definitions(definitions);
+        report.merge(&evaluate(&data_unit, &test_set, top_k));
+    }
+
+    report
+}
@@ -1,7 +1,8 @@
 use crate::beorc::def::Trace;
 use crate::LivingDataUnit;
+use crate::beorc::database::QuickTrace;
 
-use crate::cos_between;
+use crate::cos_between_f64;
 use crate::close_enough_f64;
 
 // To improve performance and usage, if the method is finalized, the dictionary check should be split
@@ -31,6 +32,36 @@ impl Prediction {
     }
 }
 
+/// Likeness below which a candidate is weak enough to be dropped by [filter_worst_predictions]. Likeness goes up to
+/// 3.0, the sum of the trace, the offset and the descriptors, and this is half of it.
+///
+const WEAK_LIKENESS: f64 = 1.5;
+
+/// Drop the worst predictions, those not above the lowest likeness found by the first 10 times a candidate
+/// scored lower than every candidate before it, starting from [WEAK_LIKENESS]. Candidates scoring above it are
+/// always kept.
+///
+pub(crate) fn filter_worst_predictions(predictions: Vec<Prediction>) -> Vec<Prediction> {
+    let mut worst_of_10: f64 = WEAK_LIKENESS;
+    let mut worst_of_count: usize = 0;
+
+    for entry in &predictions {
+        if worst_of_count < 10 && entry.likeness < worst_of_10 {
+            worst_of_10 = entry.likeness;
+            worst_of_count += 1;
+        }
+    }
+
+    let mut filtered_predictions: Vec<Prediction> = Vec::new();
+    for entry in predictions {
+        if entry.likeness > worst_of_10 {
+            filtered_predictions.push(entry);
+        }
+    }
+
+    filtered_predictions
+}
+
 impl Medium {
     pub fn new(data_unit: LivingDataUnit) -> Medium {
         Medium {
@@ -70,47 +101,49 @@ impl Medium {
             return;
         }
 
-        let compare_trace = &self.last_trace.trace;
-        let compare_average = &self.last_trace.average_offset;
+        let candidates = &self.data_unit.trace_groups[search_time_stamp].group_content;
+        let likenesses = self.score_candidates(candidates);
 
         let mut partial_predictions: Vec<Prediction> = Vec::new();
-        let mut best_value: f64 = -1.0;
-        let mut worst_of_10: f64 = 1.0;
-        let mut worst_of_count: usize = 0;
-
-        let mut trace_likeness: f64 = 0.0;
-        let mut average_likeness: f64 = 0.0;
-        let mut total_likeness: f64 = 0.0;
-        for entry in &self.data_unit.trace_groups[search_time_stamp].group_content {
-            trace_likeness = cos_between(&compare_trace, &entry.trace);
-            average_likeness = cos_between(&compare_average, &entry.average);
-
-            if trace_likeness < 0.0 {
-                trace_likeness = 0.0;
-            }
-            if average_likeness < 0.0 {
-                average_likeness = 0.0;
-            }
-            total_likeness = trace_likeness + average_likeness;
+        for (entry, total_likeness) in candidates.iter().zip(likenesses) {
+            partial_predictions.push(Prediction::new(entry.id.to_string(), total_likeness));
+        }
 
-            if total_likeness > best_value {
-                best_value = total_likeness;
-            }
-            if worst_of_count < 10 && total_likeness < worst_of_10 {
-                worst_of_10 = total_likeness;
-                worst_of_count += 1;
-            }
+        self.update_predictions(filter_worst_predictions(partial_predictions));
+    }
 
-            partial_predictions.push(Prediction::new(entry.id.to_string(), total_likeness));
+    /// Likeness of the last trace to every candidate, in the same order. With the "parallel" feature the
+    /// candidates are scored on rayon's global pool.
+    ///
+    fn score_candidates(&self, candidates: &[QuickTrace]) -> Vec<f64> {
+        #[cfg(feature = "parallel")]
+        {
+            use rayon::prelude::*;
+            candidates.par_iter().map(|entry| self.likeness_to(entry)).collect()
         }
 
-        let mut filtered_predictions: Vec<Prediction> = Vec::new();
-        for entry in partial_predictions {
-            if entry.likeness > worst_of_10 {
-                filtered_predictions.push(entry);
-            }
+        #[cfg(not(feature = "parallel"))]
+        {
+            candidates.iter().map(|entry| self.likeness_to(entry)).collect()
         }
-        self.update_predictions(filtered_predictions);
+    }
+
+    /// Sum of the likeness of the precise trace, the precise offset and the shape descriptors, the cosines
+    /// taken as 0 when negative
+    ///
+    fn likeness_to(&self, entry: &QuickTrace) -> f64 {
+        let mut trace_likeness = cos_between_f64(&self.last_trace.precise_trace, &entry.precise_trace);
+        let mut average_likeness = cos_between_f64(&self.last_trace.precise_offset, &entry.precise_average);
+        let shape_likeness = self.last_trace.descriptors.likeness(&entry.descriptors);
+
+        if trace_likeness < 0.0 {
+            trace_likeness = 0.0;
+        }
+        if average_likeness < 0.0 {
+            average_likeness = 0.0;
+        }
+
+        trace_likeness + average_likeness + shape_likeness
     }
 
     fn update_predictions(&mut self, new_predictions: Vec<Prediction>) {
@@ -1,3 +1,6 @@
 pub mod vmatrix;
 pub mod trigonometric;
-pub mod maths;
\ No newline at end of file
+pub mod maths;
+pub mod bitmatrix;
+pub mod sink;
+pub mod transform;
\ No newline at end of file
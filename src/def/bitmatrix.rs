@@ -0,0 +1,233 @@
+use crate::Vmatrix;
+
+const WORD_BITS: usize = 64;
+
+/// Square matrix holding a single bit per entry, for the data made only of 0 and 1 most of naudr works on. The
+/// entries are packed on u64 words in the same order as on a Vmatrix, so the entry (x, y) is the bit number
+/// x + y*size counting from the lowest bit of the first word. Bits past the last entry are always 0.
+///
+/// The pipelines of the crate, such as [crate::get_complete_inflexions_from_data], still work on Vmatrix. Pack the
+/// data with [BitMatrix::from_vmatrix] to call the packed operations directly, such as
+/// [crate::naudr::recurrent::recurrent_trace_bits].
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct BitMatrix {
+    pub size: usize,
+    pub words: Vec<u64>,
+}
+
+impl BitMatrix {
+    /// Create a new BitMatrix with every entry set to 0
+    ///
+    pub fn new(size: usize) -> BitMatrix {
+        BitMatrix {
+            size,
+            words: vec![0; (size * size).div_ceil(WORD_BITS)],
+        }
+    }
+
+    /// Pack a Vmatrix, where any entry different from zero (the default value of its type) is set
+    ///
+    pub fn from_vmatrix<T>(input_data: &Vmatrix<T>) -> BitMatrix
+    where
+        T: Clone + Copy + Default + PartialEq,
+    {
+        let mut result = BitMatrix::new(input_data.size);
+        for (index, entry) in input_data.data.iter().enumerate() {
+            if *entry != T::default() {
+                result.words[index / WORD_BITS] |= 1 << (index % WORD_BITS);
+            }
+        }
+
+        result
+    }
+
+    /// Unpack the data on a Vmatrix, with 1 on the entries that are set and 0 everywhere else
+    ///
+    pub fn to_vmatrix(&self) -> Vmatrix<u32> {
+        Vmatrix::build(self.size, (0..self.len()).map(|index| self.get_bit(index) as u32).collect())
+    }
+
+    /// Number of entries on the matrix, this is size*size
+    ///
+    pub fn len(&self) -> usize {
+        self.size * self.size
+    }
+
+    /// Check if the matrix has no entries at all
+    ///
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Read the entry at the given index, as found on a Vmatrix
+    ///
+    /// # Panics
+    ///
+    /// The index must be within the matrix
+    ///
+    pub fn get_bit(&self, index: usize) -> bool {
+        if index >= self.len() {
+            panic!("Index {} is out of a BitMatrix of {} entries", index, self.len());
+        }
+
+        (self.words[index / WORD_BITS] >> (index % WORD_BITS)) & 1 == 1
+    }
+
+    /// See [BitMatrix::get_bit]. Write the entry at the given index.
+    ///
+    pub fn set_bit(&mut self, index: usize, value: bool) {
+        if index >= self.len() {
+            panic!("Index {} is out of a BitMatrix of {} entries", index, self.len());
+        }
+
+        let mask: u64 = 1 << (index % WORD_BITS);
+        if value {
+            self.words[index / WORD_BITS] |= mask;
+        }
+        else {
+            self.words[index / WORD_BITS] &= !mask;
+        }
+    }
+
+    /// Read the entry on column x and row y, None if out of the matrix
+    ///
+    pub fn get(&self, x: usize, y: usize) -> Option<bool> {
+        if x >= self.size || y >= self.size {
+            return None;
+        }
+
+        Some(self.get_bit(x + y * self.size))
+    }
+
+    /// Write the entry on column x and row y. Returns false if the entry is out of the matrix and nothing was written.
+    ///
+    pub fn set(&mut self, x: usize, y: usize, value: bool) -> bool {
+        if x >= self.size || y >= self.size {
+            return false;
+        }
+
+        self.set_bit(x + y * self.size, value);
+        true
+    }
+
+    /// Write every entry from index start up to, but not including, end. Whole words are written at once.
+    ///
+    pub fn fill(&mut self, start: usize, end: usize, value: bool) {
+        let end = end.min(self.len());
+        let mut index = start;
+
+        while index < end {
+            let offset = index % WORD_BITS;
+            let taken = (WORD_BITS - offset).min(end - index);
+            let mask: u64 = if taken == WORD_BITS { u64::MAX } else { ((1 << taken) - 1) << offset };
+
+            if value {
+                self.words[index / WORD_BITS] |= mask;
+            }
+            else {
+                self.words[index / WORD_BITS] &= !mask;
+            }
+
+            index += taken;
+        }
+    }
+
+    /// Number of entries set
+    ///
+    pub fn count_ones(&self) -> usize {
+        self.words.iter().map(|word| word.count_ones() as usize).sum()
+    }
+
+    /// Index of the first entry set from index start up to, but not including, end
+    ///
+    pub fn next_one(&self, start: usize, end: usize) -> Option<usize> {
+        self.next_matching(start, end, false)
+    }
+
+    /// See [BitMatrix::next_one]. Index of the first entry not set, or end if all of them are.
+    ///
+    pub fn next_zero(&self, start: usize, end: usize) -> usize {
+        self.next_matching(start, end, true).unwrap_or(end)
+    }
+
+    /// Skip whole words that can't hold what is being looked for, flipping them to look for zeros
+    ///
+    fn next_matching(&self, start: usize, end: usize, zeros: bool) -> Option<usize> {
+        let end = end.min(self.len());
+        let mut index = start;
+
+        while index < end {
+            let word = if zeros { !self.words[index / WORD_BITS] } else { self.words[index / WORD_BITS] };
+            let remaining = word >> (index % WORD_BITS);
+
+            if remaining == 0 {
+                index += WORD_BITS - index % WORD_BITS;
+                continue;
+            }
+
+            let found = index + remaining.trailing_zeros() as usize;
+            return if found < end { Some(found) } else { None };
+        }
+
+        None
+    }
+
+    /// Get a new BitMatrix where every entry takes the value of the entry offset positions after it, or 0 if
+    /// that one is out of the matrix. The data is read as a single line, so moving by one crosses from the end
+    /// of a row to the start of the next one, and moving by size gets the entry on the next row.
+    ///
+    pub fn shifted(&self, offset: isize) -> BitMatrix {
+        let mut result = BitMatrix::new(self.size);
+        let distance = offset.unsigned_abs();
+        let word_shift = distance / WORD_BITS;
+        let bit_shift = distance % WORD_BITS;
+        let length = self.words.len();
+
+        for (position, word) in result.words.iter_mut().enumerate() {
+            let read = |at: Option<usize>| at.filter(|at| *at < length).map_or(0, |at| self.words[at]);
+
+            *word = if offset >= 0 {
+                let low = read(position.checked_add(word_shift)) >> bit_shift;
+                let high = if bit_shift == 0 { 0 } else { read(position.checked_add(word_shift + 1)) << (WORD_BITS - bit_shift) };
+                low | high
+            }
+            else {
+                let high = read(position.checked_sub(word_shift)) << bit_shift;
+                let low = if bit_shift == 0 { 0 } else { read(position.checked_sub(word_shift + 1)) >> (WORD_BITS - bit_shift) };
+                high | low
+            };
+        }
+
+        result.clear_padding();
+        result
+    }
+
+    /// Get a new BitMatrix with rows and columns swapped
+    ///
+    pub fn transposed_copy(&self) -> BitMatrix {
+        let mut result = BitMatrix::new(self.size);
+
+        for y in 0..self.size {
+            let row = y * self.size;
+            let mut x = row;
+            while let Some(found) = self.next_one(x, row + self.size) {
+                result.set_bit(y + (found - row) * self.size, true);
+                x = found + 1;
+            }
+        }
+
+        result
+    }
+
+    /// Keep the bits past the last entry at 0, as operating on whole words may set them
+    ///
+    pub(crate) fn clear_padding(&mut self) {
+        let used = self.len() % WORD_BITS;
+        if used != 0 {
+            if let Some(last) = self.words.last_mut() {
+                *last &= (1 << used) - 1;
+            }
+        }
+    }
+}
@@ -1,10 +1,39 @@
 use std::fmt::Debug;
+use std::ops::{Add, Sub, Mul, Div, Neg};
 
 // There is definetely a vector class on crates, don't clutter
 
+/// Numeric types the vectors can operate with
+///
+pub trait Scalar: Copy + Debug + PartialEq + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> + Div<Output = Self> {
+    /// Get the value as a f64, used to get magnitudes
+    ///
+    fn to_f64(self) -> f64;
+}
+
+/// See [Scalar]. Numeric types that also have negative values, needed to negate vectors and to get cross products
+///
+pub trait SignedScalar: Scalar + Neg<Output = Self> {}
+
+impl<T> SignedScalar for T where T: Scalar + Neg<Output = T> {}
+
+macro_rules! impl_scalar {
+    ($($numeric:ty),*) => {
+        $(
+            impl Scalar for $numeric {
+                fn to_f64(self) -> f64 {
+                    self as f64
+                }
+            }
+        )*
+    };
+}
+
+impl_scalar!(u32, u64, usize, i32, i64, f32, f64);
+
 /// A struct to save a simple 2d vector
 ///
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Debug, PartialEq)]
 pub struct Vector2<T> 
 where
     T: Debug,
@@ -33,31 +62,99 @@ where
     }
 }
 
+impl<T> Vector2<T>
+where
+    T: Scalar,
+{
+    pub fn dot(&self, other: &Vector2<T>) -> T {
+        self.x * other.x + self.y * other.y
+    }
+
+    pub fn magnitude(&self) -> f64 {
+        self.dot(self).to_f64().sqrt()
+    }
+
+    /// Get a vector with the same direction and a magnitude of 1. The zero vector stays as (0,0), instead of
+    /// returning NaN.
+    ///
+    pub fn normalise(&self) -> Vector2<f64> {
+        let magnitude = self.magnitude();
+        if magnitude == 0.0 {
+            return Vector2::new(0.0, 0.0);
+        }
+
+        Vector2::new(self.x.to_f64() / magnitude, self.y.to_f64() / magnitude)
+    }
+}
+
+impl<T> Vector2<T>
+where
+    T: SignedScalar,
+{
+    /// Get the z coordinate of the cross product, as if both vectors were on the z = 0 plane. It is positive
+    /// when other is counterclockwise from self.
+    ///
+    pub fn cross(&self, other: &Vector2<T>) -> T {
+        self.x * other.y - self.y * other.x
+    }
+}
+
+impl<T: Scalar> Add for Vector2<T> {
+    type Output = Vector2<T>;
+
+    fn add(self, other: Vector2<T>) -> Vector2<T> {
+        Vector2::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl<T: Scalar> Sub for Vector2<T> {
+    type Output = Vector2<T>;
+
+    fn sub(self, other: Vector2<T>) -> Vector2<T> {
+        Vector2::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl<T: Scalar> Mul<T> for Vector2<T> {
+    type Output = Vector2<T>;
+
+    fn mul(self, scalar: T) -> Vector2<T> {
+        Vector2::new(self.x * scalar, self.y * scalar)
+    }
+}
+
+impl<T: Scalar> Div<T> for Vector2<T> {
+    type Output = Vector2<T>;
+
+    fn div(self, scalar: T) -> Vector2<T> {
+        Vector2::new(self.x / scalar, self.y / scalar)
+    }
+}
+
+impl<T: SignedScalar> Neg for Vector2<T> {
+    type Output = Vector2<T>;
+
+    fn neg(self) -> Vector2<T> {
+        Vector2::new(-self.x, -self.y)
+    }
+}
+
 /// Returns the sum of two integer vectors
 ///
 pub fn sum_vectors(input1: &Vector2<i32>, input2: &Vector2<i32>) -> Vector2<i32> {
-    Vector2 {
-            x: input1.x + input2.x,
-            y: input1.y + input2.y,
-    }
+    *input1 + *input2
 }
 
 /// See [sum_vectors].
 ///
 pub fn sum_i64_vectors(input1: &Vector2<i64>, input2: &Vector2<i64>) -> Vector2<i64> {
-    Vector2 {
-            x: input1.x + input2.x,
-            y: input1.y + input2.y,
-    }
+    *input1 + *input2
 }
 
 /// Returns the substraction of two i64 vectors
 ///
 pub fn sub_vectors(input1: &Vector2<i64>, input2: &Vector2<i64>) -> Vector2<i64> {
-    Vector2 {
-            x: input1.x - input2.x,
-            y: input1.y - input2.y,
-    }
+    *input1 - *input2
 }
 
 /// Returns the approximate angle between the two vector.
@@ -94,12 +191,47 @@ pub fn cos_between(input1: &Vector2<i64>, input2: &Vector2<i64>) -> f64 {
 /// Returns a scaled vector from the input
 ///
 pub fn scale_vector(input: &Vector2<i64>, scale_factor: i64) -> Vector2<i64> {
-    let mut new_x: f64 = input.x as f64 / scale_factor as f64;
-    let mut new_y: f64 = input.y as f64 / scale_factor as f64;
-    new_x = new_x.floor();
-    new_y = new_y.floor();
+    let scaled = Vector2::new(input.x as f64, input.y as f64) / scale_factor as f64;
+
+    Vector2::new(scaled.x.floor() as i64, scaled.y.floor() as i64)
+}
 
-    Vector2::new(new_x as i64, new_y as i64)
+/// See [sum_vectors]. Keeps the decimals that integer vectors lose.
+///
+pub fn sum_f64_vectors(input1: &Vector2<f64>, input2: &Vector2<f64>) -> Vector2<f64> {
+    *input1 + *input2
+}
+
+/// See [sub_vectors].
+///
+pub fn sub_f64_vectors(input1: &Vector2<f64>, input2: &Vector2<f64>) -> Vector2<f64> {
+    *input1 - *input2
+}
+
+/// See [scale_vector]. The result is not floored.
+///
+pub fn scale_f64_vector(input: &Vector2<f64>, scale_factor: f64) -> Vector2<f64> {
+    *input / scale_factor
+}
+
+/// See [cos_between]. The same values are returned when any of the vectors is (0,0).
+///
+pub fn cos_between_f64(input1: &Vector2<f64>, input2: &Vector2<f64>) -> f64 {
+    let first_is_zero = input1.x == 0.0 && input1.y == 0.0;
+    let second_is_zero = input2.x == 0.0 && input2.y == 0.0;
+
+    if first_is_zero || second_is_zero {
+        if first_is_zero && second_is_zero {
+            return 1.0;
+        }
+        return -2.0;
+    }
+
+    let dot_product = input1.x * input2.x + input1.y * input2.y;
+    let magnitude_1 = (input1.x * input1.x + input1.y * input1.y).sqrt();
+    let magnitude_2 = (input2.x * input2.x + input2.y * input2.y).sqrt();
+
+    dot_product / (magnitude_1 * magnitude_2)
 }
 
 /// Get the distance between two indexes within a matrix.
@@ -270,7 +402,7 @@ pub fn array_position_vector_displacement(input_index: usize, row_size: usize, d
 
 /// A struct to save a simple 3d vector
 ///
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Debug, PartialEq)]
 pub struct Vector3<T> 
 where
     T: Debug,
@@ -299,4 +431,79 @@ where
     pub fn equals(&self, other: &Vector3<T>) -> bool {
         return self.x == other.x && self.y == other.y && self.z == other.z;
     }
-}
\ No newline at end of file
+}
+
+impl<T> Vector3<T>
+where
+    T: Scalar,
+{
+    pub fn dot(&self, other: &Vector3<T>) -> T {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+    pub fn magnitude(&self) -> f64 {
+        self.dot(self).to_f64().sqrt()
+    }
+
+    /// See [Vector2::normalise].
+    ///
+    pub fn normalise(&self) -> Vector3<f64> {
+        let magnitude = self.magnitude();
+        if magnitude == 0.0 {
+            return Vector3::new(0.0, 0.0, 0.0);
+        }
+
+        Vector3::new(self.x.to_f64() / magnitude, self.y.to_f64() / magnitude, self.z.to_f64() / magnitude)
+    }
+}
+
+impl<T> Vector3<T>
+where
+    T: SignedScalar,
+{
+    pub fn cross(&self, other: &Vector3<T>) -> Vector3<T> {
+        Vector3::new(self.y * other.z - self.z * other.y,
+                     self.z * other.x - self.x * other.z,
+                     self.x * other.y - self.y * other.x)
+    }
+}
+
+impl<T: Scalar> Add for Vector3<T> {
+    type Output = Vector3<T>;
+
+    fn add(self, other: Vector3<T>) -> Vector3<T> {
+        Vector3::new(self.x + other.x, self.y + other.y, self.z + other.z)
+    }
+}
+
+impl<T: Scalar> Sub for Vector3<T> {
+    type Output = Vector3<T>;
+
+    fn sub(self, other: Vector3<T>) -> Vector3<T> {
+        Vector3::new(self.x - other.x, self.y - other.y, self.z - other.z)
+    }
+}
+
+impl<T: Scalar> Mul<T> for Vector3<T> {
+    type Output = Vector3<T>;
+
+    fn mul(self, scalar: T) -> Vector3<T> {
+        Vector3::new(self.x * scalar, self.y * scalar, self.z * scalar)
+    }
+}
+
+impl<T: Scalar> Div<T> for Vector3<T> {
+    type Output = Vector3<T>;
+
+    fn div(self, scalar: T) -> Vector3<T> {
+        Vector3::new(self.x / scalar, self.y / scalar, self.z / scalar)
+    }
+}
+
+impl<T: SignedScalar> Neg for Vector3<T> {
+    type Output = Vector3<T>;
+
+    fn neg(self) -> Vector3<T> {
+        Vector3::new(-self.x, -self.y, -self.z)
+    }
+}
@@ -0,0 +1,84 @@
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+/// Destination for the debug artefacts of a process, such as the intermediate matrices of
+/// [crate::naudr::accumulate::get_accumulation] or the report of [crate::beorc::def::TrainingUnit::train_w_report_on].
+/// The library never writes on its own, every artefact goes through the sink it is given.
+///
+pub trait DebugSink {
+    /// Receive an artefact, where the name is the one of the file it would be written to
+    ///
+    fn write(&self, name: &str, content: &str);
+}
+
+/// Sink that drops every artefact, for when nothing has to be kept
+///
+#[derive(Clone, Copy, Debug, Default)]
+pub struct NullSink;
+
+impl DebugSink for NullSink {
+    fn write(&self, _name: &str, _content: &str) {}
+}
+
+/// Sink that writes every artefact as a file within a directory, creating it if needed
+///
+/// # Errors
+///
+/// Artefacts are only meant to help debugging, so a file that can't be written doesn't stop the process. The
+/// error is reported on the standard error output instead.
+///
+#[derive(Clone, Debug)]
+pub struct DirectorySink {
+    pub directory: PathBuf,
+}
+
+impl DirectorySink {
+    pub fn new<P: Into<PathBuf>>(directory: P) -> DirectorySink {
+        DirectorySink {
+            directory: directory.into(),
+        }
+    }
+}
+
+impl DebugSink for DirectorySink {
+    fn write(&self, name: &str, content: &str) {
+        let path = self.directory.join(name);
+        let written = fs::create_dir_all(&self.directory).and_then(|_| fs::write(&path, content));
+
+        if let Err(error) = written {
+            eprintln!("Debug artefact {} couldn't be written: {}", path.display(), error);
+        }
+    }
+}
+
+/// Sink that keeps every artefact in memory, in the order they were written
+///
+#[derive(Debug, Default)]
+pub struct MemorySink {
+    entries: Mutex<Vec<(String, String)>>,
+}
+
+impl MemorySink {
+    pub fn new() -> MemorySink {
+        MemorySink::default()
+    }
+
+    /// Every artefact received so far, as (name, content)
+    ///
+    pub fn entries(&self) -> Vec<(String, String)> {
+        self.entries.lock().expect("No writer panicked while holding the sink").clone()
+    }
+
+    /// Content of the last artefact received with the given name
+    ///
+    pub fn get(&self, name: &str) -> Option<String> {
+        self.entries().into_iter().rev().find(|(entry_name, _)| entry_name == name).map(|(_, content)| content)
+    }
+}
+
+impl DebugSink for MemorySink {
+    fn write(&self, name: &str, content: &str) {
+        self.entries.lock().expect("No writer panicked while holding the sink").push((name.to_string(), content.to_string()));
+    }
+}
@@ -0,0 +1,165 @@
+use crate::Vmatrix;
+use crate::Vector2;
+
+/// Rotation of a Vmatrix, drawn with the first row on top as done everywhere on naudr
+///
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum Rotation {
+    /// 90 degrees clockwise, the first row becomes the last column
+    ///
+    RIGHT,
+    /// 180 degrees, the first row becomes the last row read backwards
+    ///
+    HALF,
+    /// 90 degrees counterclockwise, or 270 clockwise, the first row becomes the first column read backwards
+    ///
+    LEFT,
+}
+
+impl<T> Vmatrix<T>
+where
+    T: Clone + Copy,
+{
+    /// Get a new Vmatrix rotated. Turning right is the same as transposing the data and flipping it horizontally.
+    ///
+    pub fn rotated_copy(&self, rotation: Rotation) -> Vmatrix<T> {
+        let last = self.size.saturating_sub(1);
+
+        match rotation {
+            Rotation::RIGHT => self.sampled(self.size, |x, y| (y, last - x)),
+            Rotation::HALF => self.sampled(self.size, |x, y| (last - x, last - y)),
+            Rotation::LEFT => self.sampled(self.size, |x, y| (last - y, x)),
+        }
+    }
+
+    /// Get a new Vmatrix mirrored left to right, so every row is read backwards
+    ///
+    pub fn flipped_horizontal(&self) -> Vmatrix<T> {
+        let last = self.size.saturating_sub(1);
+        self.sampled(self.size, |x, y| (last - x, y))
+    }
+
+    /// Get a new Vmatrix mirrored top to bottom, so the rows come in the opposite order
+    ///
+    pub fn flipped_vertical(&self) -> Vmatrix<T> {
+        let last = self.size.saturating_sub(1);
+        self.sampled(self.size, |x, y| (x, last - y))
+    }
+
+    /// Get a new Vmatrix with every entry moved offset_x columns to the right and offset_y rows down, negative
+    /// offsets moving it left and up. Entries moved out of the matrix are lost, and the ones left behind get
+    /// the fill value.
+    ///
+    pub fn translated(&self, offset_x: isize, offset_y: isize, fill: T) -> Vmatrix<T> {
+        self.sampled_or(self.size, fill, |x, y| {
+            // No matrix is big enough to keep anything moved by isize::MIN, which has no positive counterpart
+            let source_x = x.checked_add_signed(offset_x.checked_neg()?)?;
+            let source_y = y.checked_add_signed(offset_y.checked_neg()?)?;
+            Some((source_x, source_y))
+        })
+    }
+
+    /// Get a new square Vmatrix of the given size, taken from this one starting at column x and row y. A Vmatrix
+    /// is always square, so there is no crop with different width and height, see [Vmatrix::cropped_to_content]
+    /// for a box filled up to a square.
+    ///
+    /// None if the square doesn't fit within this matrix.
+    ///
+    pub fn cropped(&self, x: usize, y: usize, size: usize) -> Option<Vmatrix<T>> {
+        let fits = |start: usize| start.checked_add(size).is_some_and(|end| end <= self.size);
+        if !fits(x) || !fits(y) {
+            return None;
+        }
+
+        Some(self.sampled(size, |column, row| (column + x, row + y)))
+    }
+
+    /// Get a new Vmatrix with a margin of the given width all around this one, filled with the fill value. The
+    /// entry (x, y) ends at (x + margin, y + margin).
+    ///
+    pub fn padded(&self, margin: usize, fill: T) -> Vmatrix<T> {
+        self.sampled_or(self.size + 2 * margin, fill, |x, y| {
+            let source_x = x.checked_sub(margin)?;
+            let source_y = y.checked_sub(margin)?;
+            Some((source_x, source_y))
+        })
+    }
+
+    /// Build a new Vmatrix of the given size where every entry (x, y) is taken from the entry of this one found by
+    /// the source function
+    ///
+    fn sampled<F>(&self, size: usize, source: F) -> Vmatrix<T>
+    where
+        F: Fn(usize, usize) -> (usize, usize),
+    {
+        let data = (0..(size * size)).map(|index| {
+            let (x, y) = source(index % size, index / size);
+            self.data[x + y * self.size]
+        }).collect();
+
+        Vmatrix::build(size, data)
+    }
+
+    /// See [Vmatrix::sampled]. Entries with no source, or with a source out of this matrix, get the fill value.
+    ///
+    fn sampled_or<F>(&self, size: usize, fill: T, source: F) -> Vmatrix<T>
+    where
+        F: Fn(usize, usize) -> Option<(usize, usize)>,
+    {
+        let data = (0..(size * size)).map(|index| {
+            match source(index % size, index / size) {
+                Some((x, y)) if x < self.size && y < self.size => self.data[x + y * self.size],
+                _ => fill,
+            }
+        }).collect();
+
+        Vmatrix::build(size, data)
+    }
+}
+
+impl<T> Vmatrix<T>
+where
+    T: Clone + Copy + Default + PartialEq,
+{
+    /// Top left and bottom right corners of the box containing every entry different from zero (the default
+    /// value of its type), as (column, row). None if there are no such entries.
+    ///
+    pub fn content_bounds(&self) -> Option<(Vector2<usize>, Vector2<usize>)> {
+        let mut bounds: Option<(Vector2<usize>, Vector2<usize>)> = None;
+
+        for (x, y, value) in self.iter_coords() {
+            if value == T::default() {
+                continue;
+            }
+
+            bounds = Some(match bounds {
+                None => (Vector2::new(x, y), Vector2::new(x, y)),
+                Some((minimum, maximum)) => (
+                    Vector2::new(minimum.x.min(x), minimum.y.min(y)),
+                    Vector2::new(maximum.x.max(x), maximum.y.max(y)),
+                ),
+            });
+        }
+
+        bounds
+    }
+
+    /// Get a new Vmatrix holding only the box given by [Vmatrix::content_bounds], on its top left corner. The box
+    /// is made square by taking its longest side, and whatever the box doesn't cover gets the fill value. With no
+    /// content at all the result has size 0.
+    ///
+    pub fn cropped_to_content(&self, fill: T) -> Vmatrix<T> {
+        match self.content_bounds() {
+            None => Vmatrix::new(0),
+            Some((minimum, maximum)) => {
+                let size = (maximum.x - minimum.x).max(maximum.y - minimum.y) + 1;
+                self.sampled_or(size, fill, |x, y| {
+                    if x + minimum.x > maximum.x || y + minimum.y > maximum.y {
+                        return None;
+                    }
+                    Some((x + minimum.x, y + minimum.y))
+                })
+            }
+        }
+    }
+}
@@ -1,3 +1,5 @@
+use crate::Vector2;
+
 /// Represent the directions on a plane as the corresponding trigonometric functions. The order chosen
 /// follows the derivative of the functions so (cos)'=nsin ; (nsin)'=ncos and so on.
 ///
@@ -79,4 +81,102 @@ impl Trigonometric {
 
         result
     }
-}
\ No newline at end of file
+}
+
+/// Represent the eight compass directions on a matrix, where N is the row "above" and E the column on the
+/// "right". The order goes clockwise, following [Trigonometric], so E, S, W and N are the same directions as
+/// COS, NSIN, NCOS and SIN and the diagonals sit between them.
+///
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum Cardinal {
+    E = 0,
+    SE = 1,
+    S = 2,
+    SW = 3,
+    W = 4,
+    NW = 5,
+    N = 6,
+    NE = 7,
+}
+
+impl Cardinal {
+    /// Get cardinal enum value from an integer
+    ///
+    /// # Panics
+    ///
+    /// You have to use a value possible for the enum, this is any value in range [0-7]
+    ///
+    pub fn from_int(value: usize) -> Cardinal {
+        match value {
+            0 => Cardinal::E,
+            1 => Cardinal::SE,
+            2 => Cardinal::S,
+            3 => Cardinal::SW,
+            4 => Cardinal::W,
+            5 => Cardinal::NW,
+            6 => Cardinal::N,
+            7 => Cardinal::NE,
+            other => {
+                panic!("Value passed couldn't be converted. Values must be in range [0==7]. Your value was {other}");
+            }
+        }
+    }
+
+    /// Get the direction on the axis matching a [Trigonometric] value
+    ///
+    pub fn from_trigonometric(direction: &Trigonometric) -> Cardinal {
+        match direction {
+            Trigonometric::COS => Cardinal::E,
+            Trigonometric::NSIN => Cardinal::S,
+            Trigonometric::NCOS => Cardinal::W,
+            Trigonometric::SIN => Cardinal::N,
+        }
+    }
+
+    /// Rotate the direction by steps of 45 degrees. Positive steps go clockwise, in the same way
+    /// [Trigonometric::derivative] does, so rotate(2) is the derivative and rotate(-2) the antiderivative.
+    ///
+    pub fn rotate(&self, steps: i32) -> Cardinal {
+        let int_value = (*self as i32 + steps).rem_euclid(8);
+        Cardinal::from_int(int_value as usize)
+    }
+
+    pub fn opposite(&self) -> Cardinal {
+        self.rotate(4)
+    }
+
+    /// Check if the direction is one of the diagonals
+    ///
+    pub fn is_diagonal(&self) -> bool {
+        (*self as usize) % 2 == 1
+    }
+
+    /// Get the displacement of a single step in this direction, as (columns, rows)
+    ///
+    pub fn as_delta(&self) -> Vector2<i32> {
+        match self {
+            Cardinal::E =>  Vector2::new(1, 0),
+            Cardinal::SE => Vector2::new(1, 1),
+            Cardinal::S =>  Vector2::new(0, 1),
+            Cardinal::SW => Vector2::new(-1, 1),
+            Cardinal::W =>  Vector2::new(-1, 0),
+            Cardinal::NW => Vector2::new(-1, -1),
+            Cardinal::N =>  Vector2::new(0, -1),
+            Cardinal::NE => Vector2::new(1, -1),
+        }
+    }
+
+    /// See [Trigonometric::get_index_from_direction]. The result is not checked against the size of the data,
+    /// so moving across the first or last column still lands on the next or previous row. Moving before the
+    /// first index returns usize::MAX, which will never be a valid index.
+    ///
+    pub fn get_index_from_direction(starting_point: usize, row_size: usize, direction: &Cardinal) -> usize {
+        let delta = direction.as_delta();
+        let result = starting_point as i64 + delta.x as i64 + delta.y as i64 * row_size as i64;
+        if result < 0 {
+            return usize::MAX;
+        }
+
+        result as usize
+    }
+}
@@ -1,8 +1,40 @@
-use std::fs;
+use std::fs::File;
+use std::io;
+use std::io::{Read, Write};
+use std::path::Path;
+use std::ops::{Index, IndexMut};
+
+use crate::Cardinal;
+use crate::DebugSink;
+use crate::read_int_vector;
+
+/// Direction followed when walking a Vmatrix entry by entry
+///
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum Axis {
+    /// Along a row, from left to right
+    ///
+    ROWS,
+    /// Along a column, from top to bottom
+    ///
+    COLUMNS,
+}
+
+impl Axis {
+    /// Index, on a matrix of the given size, of the entry found after walking <position> entries along this axis.
+    /// Going along columns walks the matrix as if it was transposed, without copying it.
+    ///
+    pub fn index_on(&self, position: usize, size: usize) -> usize {
+        match self {
+            Axis::ROWS => position,
+            Axis::COLUMNS => (position % size) * size + position / size,
+        }
+    }
+}
 
 /// Basic structure of mvecops, stores the size of a "row" and all the data in a generic vector
 ///
-#[derive(Clone)]
+#[derive(Clone, Debug)]
 pub struct Vmatrix<T>
 where
     T: Clone,
@@ -73,13 +105,10 @@ where
     /// Rearrange the data so columns are read as files.
     ///
     pub fn transpose(&mut self) {
-        let data_copy = self.data.clone();
-        let size = self.size;
+        let data_copy = Vmatrix::build_nomove(self.size, &self.data);
 
-        for i in 0..size {
-            for j in 0..size {
-                self.data[i + j * self.size] = data_copy[j + i * size];
-            }
+        for (x, y, value) in data_copy.iter_coords() {
+            self[(y, x)] = value;
         }
     }
 
@@ -126,11 +155,92 @@ where
         return true
     }
 
+    /// Get the index next to another one in the given direction, or None when that step leaves the matrix.
+    /// Moving across the first or last column never lands on another row, unlike
+    /// [Cardinal::get_index_from_direction].
+    ///
+    pub fn neighbour(&self, index: usize, direction: &Cardinal) -> Option<usize> {
+        if !self.test_index(index) || self.size == 0 {
+            return None;
+        }
+
+        let delta = direction.as_delta();
+        let (x_value, y_value) = self.coordinates_of(index);
+        let x_value = x_value.checked_add_signed(delta.x as isize)?;
+        let y_value = y_value.checked_add_signed(delta.y as isize)?;
+
+        self.index_of(x_value, y_value)
+    }
+
+    /// Get the flat index of the entry at column x and row y, or None when it falls out of the matrix
+    ///
+    pub fn index_of(&self, x: usize, y: usize) -> Option<usize> {
+        if x >= self.size {
+            return None;
+        }
+
+        let index = x + y * self.size;
+        if !self.test_index(index) {
+            return None;
+        }
+
+        Some(index)
+    }
+
+    /// Get the (column, row) of a flat index
+    ///
+    pub fn coordinates_of(&self, index: usize) -> (usize, usize) {
+        (index % self.size, index / self.size)
+    }
+
+    /// Get the value at column x and row y, if there is such an entry
+    ///
+    pub fn get(&self, x: usize, y: usize) -> Option<T> {
+        self.index_of(x, y).map(|index| self.data[index])
+    }
+
+    /// Set the value at column x and row y. Returns false, leaving the data untouched, if there is no such entry.
+    ///
+    pub fn set(&mut self, x: usize, y: usize, value: T) -> bool {
+        match self.index_of(x, y) {
+            Some(index) => {
+                self.data[index] = value;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Iterate over the columns of the matrix, from the left one. Columns aren't contiguous on the data, so
+    /// every one of them is copied.
+    ///
+    pub fn cols(&self) -> impl Iterator<Item = Vec<T>> + '_ {
+        (0..self.size).map(move |x| self.data.iter().skip(x).step_by(self.size).copied().collect())
+    }
+
+    /// Iterate over every entry along with its column and row, as (x, y, value)
+    ///
+    pub fn iter_coords(&self) -> impl Iterator<Item = (usize, usize, T)> + '_ {
+        self.data.iter().enumerate().map(move |(index, value)| (index % self.size, index / self.size, *value))
+    }
+
+    /// Iterate over the indexes surrounding the given one, going clockwise from the one on its right. Entries
+    /// out of the matrix are skipped, see [neighbour].
+    ///
+    pub fn neighbours(&self, index: usize) -> impl Iterator<Item = usize> + '_ {
+        (0..8).filter_map(move |direction| self.neighbour(index, &Cardinal::from_int(direction)))
+    }
+
+    /// See [neighbours]. Only the indexes on the same row or column as the given one.
+    ///
+    pub fn axis_neighbours(&self, index: usize) -> impl Iterator<Item = usize> + '_ {
+        (0..8).step_by(2).filter_map(move |direction| self.neighbour(index, &Cardinal::from_int(direction)))
+    }
+
     /// Check if a given value is an index on the border of the matrix
     ///
     pub fn test_border_index(&self, input: usize) -> bool {
-        let x_value = input % self.size;
-        let y_value = input / self.size;
+        let (x_value, y_value) = self.coordinates_of(input);
 
         if x_value == 0 || x_value == (self.size - 1) {
             return true;
@@ -144,6 +254,17 @@ where
     }
 }
 
+impl<T> Vmatrix<T>
+where
+    T: Clone,
+{
+    /// Iterate over the rows of the matrix, from the top one
+    ///
+    pub fn rows(&self) -> std::slice::Chunks<'_, T> {
+        self.data.chunks(self.size.max(1))
+    }
+}
+
 impl<T> Vmatrix<T> 
 where
     T: ToString + Clone,
@@ -151,16 +272,42 @@ where
     /// Write a whole Vmatrix to a file, inserting a new line after <size> number of characters were written
     ///
     pub fn write_to_file(&self, file_path: String) {
+        let _ = self.save(file_path);
+    }
+
+    /// See [Vmatrix::to_text]. Write the matrix to any writer, such as a file or a socket. Entries are written
+    /// one after another with nothing between them, so [Vmatrix::read_from] only gets the same matrix back when
+    /// every entry is a single digit, as on the 0 and 1 data naudr works on.
+    ///
+    pub fn write_to<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        writer.write_all(self.to_text().as_bytes())
+    }
+
+    /// See [Vmatrix::write_to]. Write the matrix to a file with exactly the given path.
+    ///
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        self.write_to(&mut File::create(path)?)
+    }
+
+    /// See [write_to_file]. Hand the matrix to a sink instead, as an artefact with the given name.
+    ///
+    pub fn write_to_sink(&self, sink: &dyn DebugSink, name: &str) {
+        sink.write(name, &self.to_text());
+    }
+
+    /// Every row of the matrix on its own line, as written by [write_to_file]
+    ///
+    pub fn to_text(&self) -> String {
         let mut complete_output: String = String::from("");
-        let size = self.size;
 
-        for i in 0..size {
-            for j in 0..size {
-                complete_output += &self.data[j + i * size].to_string();
-            }    
+        for row in self.rows() {
+            for entry in row {
+                complete_output += &entry.to_string();
+            }
             complete_output += &String::from("\n");
         }
-        fs::write(file_path, complete_output);
+
+        complete_output
     }
 
     /// See [write_to_file]. Use a standard composed name: <file_name>#<integer>.txt. Allows to easily print out
@@ -172,15 +319,70 @@ where
                            &id.to_string() +
                            &String::from(".txt");
 
-        let mut complete_output: String = String::from("");
-        let size = self.size;
+        let _ = self.save(composed_name);
+    }
+}
 
-        for i in 0..size {
-            for j in 0..size {
-                complete_output += &self.data[j + i * size].to_string();
-            }    
-            complete_output += &String::from("\n");
+impl Vmatrix<u32> {
+    /// Read a matrix from any reader, such as a file or a byte slice embedded on the binary, taking every digit as
+    /// an entry and skipping everything else, as written by [Vmatrix::write_to].
+    ///
+    /// # Errors
+    ///
+    /// Besides the errors of the reader, the data is invalid if it doesn't have size*size digits
+    ///
+    pub fn read_from<R: Read>(reader: R, size: usize) -> io::Result<Vmatrix<u32>> {
+        let all_data = read_int_vector(reader)?;
+
+        if all_data.len() != size * size {
+            return Err(io::Error::new(io::ErrorKind::InvalidData,
+                format!("A matrix of size {} needs {} entries, {} were found", size, size * size, all_data.len())));
         }
-        fs::write(composed_name, complete_output);
+
+        Ok(Vmatrix::build(size, all_data))
     }
-}
\ No newline at end of file
+
+    /// See [Vmatrix::read_from]. Read the matrix from a file with exactly the given path.
+    ///
+    pub fn load<P: AsRef<Path>>(path: P, size: usize) -> io::Result<Vmatrix<u32>> {
+        Vmatrix::read_from(File::open(path)?, size)
+    }
+}
+
+impl<T> Index<(usize, usize)> for Vmatrix<T>
+where
+    T: Clone,
+{
+    type Output = T;
+
+    /// Get the entry at (column, row)
+    ///
+    /// # Panics
+    ///
+    /// The coordinates have to be within the matrix
+    ///
+    fn index(&self, coordinates: (usize, usize)) -> &T {
+        let (x, y) = coordinates;
+        if x >= self.size {
+            panic!("Column {x} is out of a matrix of size {}", self.size);
+        }
+
+        &self.data[x + y * self.size]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Vmatrix<T>
+where
+    T: Clone,
+{
+    /// See [Index]. Get the entry at (column, row) to modify it
+    ///
+    fn index_mut(&mut self, coordinates: (usize, usize)) -> &mut T {
+        let (x, y) = coordinates;
+        if x >= self.size {
+            panic!("Column {x} is out of a matrix of size {}", self.size);
+        }
+
+        &mut self.data[x + y * self.size]
+    }
+}
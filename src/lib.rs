@@ -2,13 +2,17 @@ use std::error::Error;
 
 use std::vec::Vec;
 
-use std::fs;
+use std::fs::File;
+use std::io;
+use std::io::Read;
 
 pub mod def;
 pub mod naudr;
 pub mod beorc;
 
 use def::vmatrix::*;
+use def::bitmatrix::*;
+use def::sink::*;
 use def::trigonometric::*;
 use def::maths::*;
 use naudr::accumulate::*;
@@ -16,6 +20,7 @@ use naudr::recurrent::*;
 use naudr::operate::*;
 use naudr::closed_curves::*;
 use naudr::bloat::*;
+use naudr::svg::SvgReport;
 
 use beorc::def::DefinitionUnit;
 use beorc::def::TrainingUnit;
@@ -29,7 +34,16 @@ use beorc::def::Trace;
 
 // GENERIC //
 pub fn textfile_to_int_vector(file_path: String) -> Result<Vec<u32>, Box<dyn Error>> {
-    let file_content = fs::read_to_string(file_path)?;
+    Ok(read_int_vector(File::open(file_path)?)?)
+}
+
+/// See [textfile_to_int_vector]. Read the digits from any reader, such as a file or a byte slice embedded on the
+/// binary, skipping everything else.
+///
+pub fn read_int_vector<R: Read>(mut reader: R) -> io::Result<Vec<u32>> {
+    let mut file_content = String::new();
+    reader.read_to_string(&mut file_content)?;
+
     let mut all_data: Vec<u32> = Vec::new();
     for character in file_content.chars() {
         match character.to_digit(10) {
@@ -54,33 +68,20 @@ pub fn textfile_to_vmatrix(file_path: String, size: usize) -> Vmatrix<u32>
 // --- //
 // API //
 
-fn write_out_sample_reductions() {
-        let sample_size: usize = 64;
-
-        let input_data: Vmatrix<u32> = textfile_to_vmatrix(SAMPLE_INPUT_PATH.to_string(), sample_size);
-  
-        let output_path = Some(SAMPLE_OUTPUT_RED);
-
-        let accumulations: Vmatrix<u32> = get_accumulation(&input_data, &output_path);
-        accumulations.write_to_file(SAMPLE_OUTPUT_ACC.to_string());
-}
-
 /// Get the reductions from data at the target file. The input is a file with values either 1 or 0
 /// that represent points where data is "found". The sample size is the number of elements per row
 /// to recreate the data internally as a matrix. Use "samplekanji.txt" as reference.
 /// 
 pub fn get_accumulations_from_file(target_file: String, sample_size: usize) -> Vmatrix<u32> {
     let input_data: Vmatrix<u32> = textfile_to_vmatrix(target_file, sample_size);
-    let output_path = None;
-    get_accumulation(&input_data, &output_path)
+    get_accumulation_from_distance(&input_data)
 }
 
 /// See [get_accumulations_from_file]. This function is meant to be used with data directly, instead
 /// of loading it through a file.
 ///
 pub fn get_accumulations_from_data(input_data: Vmatrix<u32>, sample_size: usize) -> Vmatrix<u32> {
-    let output_path = None;
-    get_accumulation(&input_data, &output_path)
+    get_accumulation_from_distance(&input_data)
 }
 
 // Is sample_size ever used??
@@ -92,16 +93,12 @@ pub fn get_accumulations_from_data(input_data: Vmatrix<u32>, sample_size: usize)
 /// heavier process to find a description.
 ///
 pub fn get_substractions_from_data(accumulations: Vmatrix<u32>, sample_size: usize, dominants_recurrency: usize) -> Vmatrix<u32> {
-    let accumulations_transposed = accumulations.transposed_copy();
-
     let vertical_dominant = recurrent_trace(&accumulations, dominants_recurrency);
-    let horizont_dominant = recurrent_trace(&accumulations_transposed, dominants_recurrency);
+    let horizont_dominant = recurrent_trace_along(&accumulations, dominants_recurrency, Axis::COLUMNS);
 
     let mut substraction_result: Vmatrix<u32> = accumulations.normal_copy();
     substraction_result = substraction_result.xat(&vertical_dominant);
-    substraction_result.transpose();
     substraction_result = substraction_result.xat(&horizont_dominant);
-    substraction_result.transpose();
 
     return substraction_result;
 }
@@ -112,22 +109,16 @@ pub fn get_inflexions_from_vector(input_data: Vec<u32>, sample_size: usize, domi
 }
 
 pub fn get_complete_inflexions_from_data(input_data: &Vmatrix<u32>, sample_size: usize, dominants_recurrency: usize) -> GlobalCurveData {
-    let mut global_curve_data = GlobalCurveData::new(sample_size);
-    global_curve_data.transpose_internal();
+    let mut global_curve_data = GlobalCurveData::new_along(sample_size, Axis::COLUMNS);
 
-    let output_path = None;
-    let accumulations: Vmatrix<u32> = get_accumulation(&input_data, &output_path);
-
-    let accumulations_transposed = accumulations.transposed_copy();
+    let accumulations: Vmatrix<u32> = get_accumulation_from_distance(input_data);
 
     let vertical_dominant = recurrent_trace(&accumulations, dominants_recurrency);
-    let horizont_dominant = recurrent_trace(&accumulations_transposed, dominants_recurrency);
+    let horizont_dominant = recurrent_trace_along(&accumulations, dominants_recurrency, Axis::COLUMNS);
 
     let mut subtractions: Vmatrix<u32> = accumulations.normal_copy();
     subtractions = subtractions.xat(&vertical_dominant);
-    subtractions.transpose();
     subtractions = subtractions.xat(&horizont_dominant);
-    subtractions.transpose();
 
     let inflexion_curves = get_curves(&mut global_curve_data, &subtractions);
 
@@ -139,12 +130,10 @@ pub fn get_complete_inflexions_from_data(input_data: &Vmatrix<u32>, sample_size:
     let mut result_set_vertical = Vmatrix::initialize(sample_size, 0);
     mark_curve_points(&vertical_inflexion, &mut result_set_vertical, &mut global_curve_data, true);
 
-    global_curve_data.transpose_internal();
-
-    let horizontal_inflexion = get_curves(&mut global_curve_data, &horizont_dominant);
+    let horizontal_inflexion = get_curves_along(&mut global_curve_data, &horizont_dominant, Axis::COLUMNS);
 
     let mut result_set_horizontal = Vmatrix::initialize(sample_size, 0);
-    mark_curve_points(&horizontal_inflexion, &mut result_set_horizontal, &mut global_curve_data, true);
+    mark_curve_points_along(&horizontal_inflexion, &mut result_set_horizontal, &mut global_curve_data, true, Axis::COLUMNS);
 
     return global_curve_data;
 }
@@ -155,19 +144,14 @@ pub fn get_curve_no_reductions(input_data: Vec<u32>, sample_size: usize, dominan
 }
 
 pub fn get_inflexions_no_reduction(input_data: &Vmatrix<u32>, sample_size: usize, dominants_recurrency: usize) -> GlobalCurveData {
-    let mut global_curve_data = GlobalCurveData::new(sample_size);
-    global_curve_data.transpose_internal();
-
-    let input_transposed = input_data.transposed_copy();
+    let mut global_curve_data = GlobalCurveData::new_along(sample_size, Axis::COLUMNS);
 
     let vertical_dominant = recurrent_trace(input_data, dominants_recurrency);
-    let horizont_dominant = recurrent_trace(&input_transposed, dominants_recurrency);
+    let horizont_dominant = recurrent_trace_along(input_data, dominants_recurrency, Axis::COLUMNS);
 
     let mut subtractions: Vmatrix<u32> = input_data.normal_copy();
     subtractions = subtractions.xat(&vertical_dominant);
-    subtractions.transpose();
     subtractions = subtractions.xat(&horizont_dominant);
-    subtractions.transpose();
 
     let inflexion_curves = get_curves(&mut global_curve_data, &subtractions);
 
@@ -179,12 +163,10 @@ pub fn get_inflexions_no_reduction(input_data: &Vmatrix<u32>, sample_size: usize
     let mut result_set_vertical = Vmatrix::initialize(sample_size, 0);
     mark_curve_points(&vertical_inflexion, &mut result_set_vertical, &mut global_curve_data, true);
 
-    global_curve_data.transpose_internal();
-
-    let horizontal_inflexion = get_curves(&mut global_curve_data, &horizont_dominant);
+    let horizontal_inflexion = get_curves_along(&mut global_curve_data, &horizont_dominant, Axis::COLUMNS);
 
     let mut result_set_horizontal = Vmatrix::initialize(sample_size, 0);
-    mark_curve_points(&horizontal_inflexion, &mut result_set_horizontal, &mut global_curve_data, true);
+    mark_curve_points_along(&horizontal_inflexion, &mut result_set_horizontal, &mut global_curve_data, true, Axis::COLUMNS);
 
     return global_curve_data;
 }
@@ -198,6 +180,13 @@ pub fn get_bloat_data(input_data: Vec<u32>, sample_size: usize) -> GlobalCurveDa
     return global_curve_data;
 }
 
+/// See [get_bloat_data]. Get only the entries left by the bloats, as (index, radius) pairs. Rebuild the data they
+/// cover with [reconstruct_from_bloats].
+///
+pub fn get_bloat_disks(input_data: Vec<u32>, sample_size: usize) -> Vec<(usize, u32)> {
+    bloat_disks(&get_bloat_data(input_data, sample_size))
+}
+
 pub fn get_combined_data(input_data: Vec<u32>, sample_size: usize, dominants_recurrency: usize) -> (GlobalCurveData, GlobalCurveData) {
     let format_input_data = Vmatrix::build(sample_size, input_data);
 
@@ -205,22 +194,23 @@ pub fn get_combined_data(input_data: Vec<u32>, sample_size: usize, dominants_rec
 }
 
 pub fn get_dominant_plus_bloat(input_data: &Vmatrix<u32>, sample_size: usize, dominants_recurrency: usize) -> (GlobalCurveData, GlobalCurveData) {
-    let mut global_curve_data = GlobalCurveData::new(sample_size);
-    global_curve_data.transpose_internal();
+    let (curve_data, bloat_data, _) = get_dominant_plus_bloat_with_mask(input_data, sample_size, dominants_recurrency);
+    (curve_data, bloat_data)
+}
 
-    // let output_path = None;
-    // let accumulations: Vmatrix<u32> = get_accumulation(&input_data, &output_path);
+/// See [get_dominant_plus_bloat]. Also get the dominant lines found along both axes, as a single mask.
+///
+fn get_dominant_plus_bloat_with_mask(input_data: &Vmatrix<u32>, sample_size: usize, dominants_recurrency: usize) -> (GlobalCurveData, GlobalCurveData, Vmatrix<u32>) {
+    let mut global_curve_data = GlobalCurveData::new_along(sample_size, Axis::COLUMNS);
 
-    let input_transposed = input_data.transposed_copy();
+    // let accumulations: Vmatrix<u32> = get_accumulation(&input_data, &NullSink);
 
-    let vertical_dominant = recurrent_trace(&input_data, dominants_recurrency);
-    let horizont_dominant = recurrent_trace(&input_transposed, dominants_recurrency);
+    let vertical_dominant = recurrent_trace(input_data, dominants_recurrency);
+    let horizont_dominant = recurrent_trace_along(input_data, dominants_recurrency, Axis::COLUMNS);
 
     let mut subtractions: Vmatrix<u32> = input_data.normal_copy();
     subtractions = subtractions.xat(&vertical_dominant);
-    subtractions.transpose();
     subtractions = subtractions.xat(&horizont_dominant);
-    subtractions.transpose();
 
     // let inflexion_curves = get_curves(&mut global_curve_data, &subtractions);
 
@@ -232,33 +222,93 @@ pub fn get_dominant_plus_bloat(input_data: &Vmatrix<u32>, sample_size: usize, do
     let mut result_set_vertical = Vmatrix::initialize(sample_size, 0);
     mark_curve_points(&vertical_inflexion, &mut result_set_vertical, &mut global_curve_data, true);
 
-    global_curve_data.transpose_internal();
-
-    let horizontal_inflexion = get_curves(&mut global_curve_data, &horizont_dominant);
+    let horizontal_inflexion = get_curves_along(&mut global_curve_data, &horizont_dominant, Axis::COLUMNS);
 
     let mut result_set_horizontal = Vmatrix::initialize(sample_size, 0);
-    mark_curve_points(&horizontal_inflexion, &mut result_set_horizontal, &mut global_curve_data, true);
+    mark_curve_points_along(&horizontal_inflexion, &mut result_set_horizontal, &mut global_curve_data, true, Axis::COLUMNS);
 
     let mut bloat_curve_data = GlobalCurveData::new(sample_size);
     write_bloats(&mut bloat_curve_data, &subtractions);
 
-    return (global_curve_data, bloat_curve_data);
+    let dominant = vertical_dominant.or(&horizont_dominant).expect("Dominant lines share the size of the input");
+
+    (global_curve_data, bloat_curve_data, dominant)
+}
+
+/// See [get_dominant_plus_bloat]. Get the dominant lines of the data as lists of segments instead of masks, as
+/// (runs along rows, runs along columns). These are the straight strokes that can be described trivially, without
+/// looking for curves on them.
+///
+pub fn get_dominant_segments(input_data: &Vmatrix<u32>, dominants_recurrency: usize) -> (Vec<RunSegment>, Vec<RunSegment>) {
+    let along_rows = recurrent_runs(input_data, dominants_recurrency, Axis::ROWS);
+    let along_columns = recurrent_runs(input_data, dominants_recurrency, Axis::COLUMNS);
+
+    (along_rows.segments, along_columns.segments)
+}
+
+/// Get a visual report of the whole process on a single glyph: the input data, its dominant lines, the curves
+/// found on them in the order they were marked and the bloats left after removing the dominant lines. Write it
+/// with [SvgReport::write_to_file] and open it on any browser, instead of reading the digits on the text files.
+///
+pub fn get_svg_report(input_data: &Vmatrix<u32>, sample_size: usize, dominants_recurrency: usize) -> SvgReport {
+    let (curve_data, bloat_data, dominant) = get_dominant_plus_bloat_with_mask(input_data, sample_size, dominants_recurrency);
+
+    let mut report = SvgReport::new(sample_size, 10.0);
+    report.add_matrix_layer("input", input_data, "#000", 0.15);
+    report.add_dominant_overlay(&dominant);
+    report.add_curves(&curve_data);
+    report.add_bloat_circles(&bloat_data);
+
+    report
+}
+
+/// See [get_complete_inflexions_from_data]. Process a whole batch of glyphs, getting the results in the same order
+/// as the inputs. With the "parallel" feature the glyphs are processed concurrently on rayon's global pool.
+///
+pub fn get_complete_inflexions_from_batch(inputs: &[Vmatrix<u32>], sample_size: usize, dominants_recurrency: usize) -> Vec<GlobalCurveData> {
+    process_batch(inputs, |input_data| get_complete_inflexions_from_data(input_data, sample_size, dominants_recurrency))
+}
+
+/// See [get_dominant_plus_bloat] and [get_complete_inflexions_from_batch].
+///
+pub fn get_dominant_plus_bloat_from_batch(inputs: &[Vmatrix<u32>], sample_size: usize, dominants_recurrency: usize) -> Vec<(GlobalCurveData, GlobalCurveData)> {
+    process_batch(inputs, |input_data| get_dominant_plus_bloat(input_data, sample_size, dominants_recurrency))
+}
+
+/// Apply the process to every input, concurrently with the "parallel" feature, keeping the order of the inputs
+///
+fn process_batch<T, R, F>(inputs: &[T], process: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync + Send,
+{
+    #[cfg(feature = "parallel")]
+    {
+        use rayon::prelude::*;
+        inputs.par_iter().map(process).collect()
+    }
+
+    #[cfg(not(feature = "parallel"))]
+    {
+        inputs.iter().map(process).collect()
+    }
 }
 
 // --- 0.2.1 --- //
 
 // --- END OF API --- //
 
-const SAMPLE_INPUT_PATH: &str = "samplekanji.txt";
-const SAMPLE_OUTPUT_RED: &str = "reduction#";
-const SAMPLE_OUTPUT_ACC: &str = "accumulations.txt";
-
 const SAMPLE_INPUT_GETQUICK: &str = "quickaccess_testinput";
 const SAMPLE_INPUT_GETHEAVY: &str = "heavyaccess_testinput";
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::beorc::evaluate::*;
+    use crate::beorc::def::TraceDescriptors;
+    use crate::beorc::def::TURNING_BINS;
+    use crate::beorc::def::DESCRIPTOR_VALUES;
 
     #[test]
     fn trace_values_on_feed() {
@@ -400,6 +450,28 @@ mod tests {
         tunit_sample.train_w_report();
     }
 
+    #[test]
+    fn trained_trace_keeps_order() {
+        let mut dunit_sample: DefinitionUnit = DefinitionUnit::new(5);
+        dunit_sample.id = String::from("Tank");
+        dunit_sample.feed(0, vec![1, 2, 3]);
+        dunit_sample.feed(1, vec![13, 12, 11]);
+        dunit_sample.feed(2, vec![21, 16, 17, 18, 19, 24, 23, 22]);
+
+        let mut tunit_sample: TrainingUnit = TrainingUnit::new(&dunit_sample, 0.5);
+        tunit_sample.feed(dunit_sample.clone());
+        tunit_sample.feed(dunit_sample.clone());
+
+        let report = MemorySink::new();
+        let trained = tunit_sample.train_w_report_on(&report);
+
+        assert!(report.get("debug_report_data.txt").is_some_and(|content| content.contains("Training complete")));
+        assert_eq!(trained.traces[1].indexes, vec![13, 12, 11]);
+        assert_eq!(trained.traces[2].indexes, vec![21, 16, 17, 18, 19, 24, 23, 22]);
+        let offset = &trained.traces[2].average_offset;
+        assert_eq!((offset.x, offset.y), (dunit_sample.traces[2].average_offset.x, dunit_sample.traces[2].average_offset.y));
+    }
+
     #[test]
     fn cosine_calculation() {
         let mut vector1: Vector2<i64> = Vector2::new(3, 0);
@@ -463,7 +535,11 @@ mod tests {
         dunit_sample.feed(2, vec![14, 19]);
         database_live.definitions.push(dunit_sample.clone());
 
-        database_live.dump_to_file(String::from("testdb"));
+        let mut quick_written: Vec<u8> = Vec::new();
+        let mut heavy_written: Vec<u8> = Vec::new();
+        database_live.write_to(&mut quick_written, &mut heavy_written).unwrap();
+        assert!(String::from_utf8(quick_written).unwrap().contains("Meteor."));
+        assert!(String::from_utf8(heavy_written).unwrap().contains("Meteor.10,6,2;"));
     }
 
     #[test]
@@ -481,11 +557,127 @@ mod tests {
         let mut vector_output: &Vector2<i64> = &database_live.trace_groups[1].group_content[1].trace;
         assert_eq!((vector_output.x, vector_output.y),(1, -3));
 
-        database_live.dump_to_file(String::from("overwrite"));
+        let mut heavy_written: Vec<u8> = Vec::new();
+        database_live.write_to(&mut Vec::new(), &mut heavy_written).unwrap();
+        assert_eq!(heavy_written, std::fs::read(SAMPLE_INPUT_GETHEAVY).unwrap());
 
         println!("The db has a consistent reading: {}", consistent);
     }
 
+    #[test]
+    fn descriptors_split_same_displacement() {
+        let straight: Trace = Trace::new(0, vec![0, 1, 2, 3, 4], 5);
+        let hook: Trace = Trace::new(0, vec![0, 5, 11, 7, 3, 4], 5);
+
+        assert_eq!(straight.trace.equals(&hook.trace), true);
+        assert_eq!(close_enough_f64(straight.descriptors.path_length, 4.0, 0.001), true);
+        assert_eq!(straight.descriptors.turning_histogram[TURNING_BINS / 2], 3);
+        assert_eq!(straight.descriptors.curvature_sign_changes, 0);
+        assert_eq!((straight.descriptors.start_quadrant, straight.descriptors.end_quadrant), (0, 1));
+
+        let hook_box = (hook.descriptors.bounding_min, hook.descriptors.bounding_max);
+        assert_eq!((hook_box.0.x, hook_box.0.y, hook_box.1.x, hook_box.1.y), (0, 0, 4, 2));
+        assert_eq!(hook.descriptors.curvature_sign_changes, 1);
+
+        assert_eq!(close_enough_f64(straight.descriptors.likeness(&straight.descriptors), 1.0, 0.001), true);
+        assert!(straight.descriptors.likeness(&hook.descriptors) < 0.8);
+    }
+
+    #[test]
+    fn descriptors_through_quick_values() {
+        let hook: Trace = Trace::new(0, vec![0, 5, 11, 7, 3, 4], 5);
+        let values = hook.descriptors.to_values();
+        let split_values: Vec<&str> = values.split(",").filter(|entry| !entry.is_empty()).collect();
+        assert_eq!(split_values.len(), DESCRIPTOR_VALUES);
+
+        let read_back = TraceDescriptors::from_values(&split_values).unwrap();
+        assert_eq!(read_back.equals(&hook.descriptors), true);
+        assert_eq!(TraceDescriptors::from_values(&split_values[1..]).is_none(), true);
+
+        let mut database_live: LivingDataUnit = LivingDataUnit::empty();
+        let consistent = database_live.load_from_file(String::from(SAMPLE_INPUT_GETQUICK), String::from(SAMPLE_INPUT_GETHEAVY), 5);
+        assert_eq!(consistent, true);
+        let quick_trace = &database_live.trace_groups[2].group_content[2];
+        assert_eq!(quick_trace.descriptors.equals(&database_live.definitions[3].traces[2].descriptors), true);
+
+        let quick_content = String::from("Hook.4,0,1,0,") + &values + &String::from(".");
+        let mut database_stored: LivingDataUnit = LivingDataUnit::empty();
+        let consistent = database_stored.load_from_data(quick_content, String::from("Hook.0,5,11,7,3,4;"), 5);
+        assert_eq!(consistent, true);
+        assert_eq!(database_stored.trace_groups[0].group_content[0].descriptors.curvature_sign_changes, 1);
+    }
+
+    #[test]
+    fn precise_offset_keeps_decimals() {
+        let hook: Trace = Trace::new(0, vec![0, 5, 11, 7, 3, 4], 5);
+        assert_eq!(hook.average_offset.equals(&Vector2::new(1, 0)), true);
+        assert_eq!(close_enough_f64(hook.precise_offset.x, 10.0 / 6.0, 0.0001), true);
+        assert_eq!(close_enough_f64(hook.precise_offset.y, 4.0 / 6.0, 0.0001), true);
+        assert_eq!(hook.precise_trace.equals(&Vector2::new(4.0, 0.0)), true);
+
+        let values = hook.descriptors.to_values();
+        let quick_content = String::from("Hook.4,0,1,0,") + &values + &String::from("4000,0,1667,667,.");
+        let mut database_stored: LivingDataUnit = LivingDataUnit::empty();
+        let consistent = database_stored.load_from_data(quick_content, String::from("Hook.0,5,11,7,3,4;"), 5);
+        assert_eq!(consistent, true);
+        assert_eq!(database_stored.trace_groups[0].group_content[0].precise_average.x, 1.667);
+
+        let quick_content = String::from("Hook.4,0,1,0,") + &values + &String::from("4000,0,1000,0,.");
+        let mut database_stored: LivingDataUnit = LivingDataUnit::empty();
+        let consistent = database_stored.load_from_data(quick_content, String::from("Hook.0,5,11,7,3,4;"), 5);
+        assert_eq!(consistent, false);
+
+        let mut database_live: LivingDataUnit = LivingDataUnit::empty();
+        database_live.load_from_file(String::from(SAMPLE_INPUT_GETQUICK), String::from(SAMPLE_INPUT_GETHEAVY), 5);
+        let quick_trace = &database_live.trace_groups[1].group_content[4];
+        let heavy_trace = &database_live.definitions[4].traces[1];
+        assert_eq!(quick_trace.precise_average.equals(&heavy_trace.precise_offset), true);
+    }
+
+    #[test]
+    fn evaluate_db_against_itself() {
+        let mut database_live: LivingDataUnit = LivingDataUnit::empty();
+        database_live.load_from_file(String::from(SAMPLE_INPUT_GETQUICK), String::from(SAMPLE_INPUT_GETHEAVY), 5);
+        let test_set = database_live.definitions.clone();
+
+        let report = evaluate(&database_live, &test_set, 3);
+        println!("{}", report);
+
+        assert_eq!(report.total, 5);
+        assert_eq!(report.confusion.iter().map(|row| row.iter().sum::<usize>()).sum::<usize>(), 5);
+        assert!(report.topk_accuracy() >= report.top1_accuracy());
+        assert_eq!(report.top1_accuracy(), 1.0);
+    }
+
+    #[test]
+    fn k_fold_on_repeated_samples() {
+        let mut database_live: LivingDataUnit = LivingDataUnit::empty();
+        database_live.load_from_file(String::from(SAMPLE_INPUT_GETQUICK), String::from(SAMPLE_INPUT_GETHEAVY), 5);
+
+        let mut samples: Vec<DefinitionUnit> = Vec::new();
+        for _ in 0..3 {
+            samples.append(&mut database_live.definitions.clone());
+        }
+
+        let report = k_fold_evaluation(&samples, 3, 2, 0.5);
+        println!("{}", report);
+
+        assert_eq!(report.total, 15);
+        assert_eq!(report.ids.len(), 5);
+        assert!(report.topk_accuracy() >= report.top1_accuracy());
+        assert_eq!(report.confusion.iter().flatten().sum::<usize>(), report.total);
+
+        // Reports are merged by label, whatever order they list them in
+        let labels = vec![String::from("a"), String::from("b")];
+        let mut merged = EvaluationReport::new(labels.clone(), 1);
+        let mut reversed = EvaluationReport::new(labels.into_iter().rev().collect(), 1);
+        reversed.record("a", &[String::from("b")]);
+        reversed.record("b", &[]);
+        merged.merge(&reversed);
+        assert_eq!(merged.confusion, vec![vec![0, 1, 0], vec![0, 0, 1]]);
+        assert_eq!((merged.total, merged.top1_hits), (2, 0));
+    }
+
     #[test]
     fn vector_equality() {
         let vector1 = Vector2::new(2, 5);
@@ -521,4 +713,79 @@ mod tests {
         partial_results = medium_instance.get_list_of_predictions();
         println!("ITERATION 2 ::: {}", print_predictions(partial_results.0, partial_results.1));
     }
+
+    #[test]
+    fn shared_across_threads() {
+        fn assert_send_sync<T: Send + Sync>() {}
+
+        assert_send_sync::<Vmatrix<u32>>();
+        assert_send_sync::<BitMatrix>();
+        assert_send_sync::<GlobalCurveData>();
+        assert_send_sync::<SvgReport>();
+        assert_send_sync::<LivingDataUnit>();
+        assert_send_sync::<Medium>();
+        assert_send_sync::<Trace>();
+    }
+
+    #[test]
+    fn database_through_readers_and_writers() {
+        let quick_bytes: &[u8] = include_bytes!("../quickaccess_testinput");
+        let heavy_bytes: &[u8] = include_bytes!("../heavyaccess_testinput");
+
+        let mut from_files: LivingDataUnit = LivingDataUnit::empty();
+        from_files.load_from_file(String::from(SAMPLE_INPUT_GETQUICK), String::from(SAMPLE_INPUT_GETHEAVY), 5);
+
+        let mut embedded: LivingDataUnit = LivingDataUnit::empty();
+        embedded.read_from(quick_bytes, heavy_bytes, 5).unwrap();
+        assert_eq!(embedded.trace_groups.len(), from_files.trace_groups.len());
+        assert_eq!(embedded.definitions.len(), from_files.definitions.len());
+
+        let mut quick_written: Vec<u8> = Vec::new();
+        let mut heavy_written: Vec<u8> = Vec::new();
+        embedded.write_to(&mut quick_written, &mut heavy_written).unwrap();
+
+        let directory = std::env::temp_dir().join(format!("mvecops_database_{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+        let quick_path = directory.join("quick.db");
+        let heavy_path = directory.join("heavy.db");
+        embedded.save(&quick_path, &heavy_path).unwrap();
+
+        let mut reloaded: LivingDataUnit = LivingDataUnit::empty();
+        reloaded.load(&quick_path, &heavy_path, 5).unwrap();
+        assert_eq!(std::fs::read(&quick_path).unwrap(), quick_written);
+
+        let mut rewritten: Vec<u8> = Vec::new();
+        reloaded.write_to(&mut rewritten, &mut Vec::new()).unwrap();
+        assert_eq!(rewritten, quick_written);
+
+        assert!(reloaded.load(directory.join("missing.db"), &heavy_path, 5).is_err());
+
+        let mut malformed: LivingDataUnit = LivingDataUnit::empty();
+        let error = malformed.read_from("".as_bytes(), "Meteor".as_bytes(), 5).unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+        let error = malformed.read_from("Meteor.1,2.".as_bytes(), "".as_bytes(), 5).unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn only_the_worst_predictions_are_filtered() {
+        use crate::beorc::medium::Prediction;
+        use crate::beorc::medium::filter_worst_predictions;
+
+        // Only the weak candidates go, the lowest one and any other not above it
+        let likenesses = [2.5, 1.2, 2.8, 0.4, 2.9, 1.0];
+        let predictions = likenesses.iter().enumerate()
+            .map(|(position, likeness)| Prediction::new(position.to_string(), *likeness))
+            .collect();
+
+        let filtered = filter_worst_predictions(predictions);
+        let kept: Vec<String> = filtered.iter().map(|entry| entry.id.clone()).collect();
+        assert_eq!(kept, vec!["0", "1", "2", "4", "5"]);
+
+        // Strong candidates always stay, even when one scores lower than the other
+        let strong = vec![Prediction::new(String::from("a"), 2.9), Prediction::new(String::from("b"), 3.0)];
+        assert_eq!(filter_worst_predictions(strong).len(), 2);
+        assert_eq!(filter_worst_predictions(vec![Prediction::new(String::from("a"), 1.6)]).len(), 1);
+    }
 }
@@ -2,4 +2,10 @@ pub mod accumulate;
 pub mod recurrent;
 pub mod operate;
 pub mod closed_curves;
-pub mod bloat;
\ No newline at end of file
+pub mod bloat;
+pub mod components;
+pub mod contours;
+pub mod vectorise;
+pub mod svg;
+pub mod lines;
+pub mod distance;
\ No newline at end of file
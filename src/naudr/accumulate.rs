@@ -1,6 +1,9 @@
 const MAXIMUM_REDUCTIONS_DECORNERING: u32 = 10;
 
 use crate::Vmatrix;
+use crate::BitMatrix;
+use crate::DebugSink;
+use crate::naudr::distance::*;
 
 // Might not belong here
 struct CountingPointer {
@@ -38,10 +41,10 @@ impl CountingPointer {
 /// that holds less and less data while preserving the shape, until any "stroke" of data is 1px wide and
 /// it dissappears.
 ///
-/// "samplekanji.txt" is used as the input sample. Running cargo test will create a file called "accumulations.txt"
-/// as a result and "reduction#[number].txt" to show the steps in between.
+/// "samplekanji.txt" is used as the input sample. Every step in between is handed to the sink as
+/// "reduction#[number].txt", pass [crate::def::sink::NullSink] to drop them.
 ///
-pub fn get_accumulation(input_data: &Vmatrix<u32>, output_path: &Option<&str>) -> Vmatrix<u32>{
+pub fn get_accumulation(input_data: &Vmatrix<u32>, sink: &dyn DebugSink) -> Vmatrix<u32>{
     let mut working_data = input_data.clone();
 
     let mut reductions: u32 = 1;
@@ -56,16 +59,7 @@ pub fn get_accumulation(input_data: &Vmatrix<u32>, output_path: &Option<&str>) -
 
         working_data = new_data;
 
-        match output_path {
-            None => (),
-            Some(target_path) => {
-                let path_name: String = 
-                    target_path.to_string() +
-                    &reductions.to_string() +
-                    &String::from(".txt");
-                working_data.write_to_file(path_name);
-            }
-        }
+        working_data.write_to_sink(sink, &format!("reduction#{}.txt", reductions));
 
         reductions += 1;
     };
@@ -73,6 +67,31 @@ pub fn get_accumulation(input_data: &Vmatrix<u32>, output_path: &Option<&str>) -
     accumulate_reductions(&accumulative_data)
 }
 
+/// See [get_accumulation]. Get the same accumulation from a single [distance_transform], without decornering
+/// the data over and over.
+///
+/// # Method
+///
+/// Every pass of [decorner_once] keeps the entries whose 8 neighbours have data, and the border is always
+/// removed, so an entry survives k passes if every entry up to k away from it has data, counting everything
+/// beyond the matrix as empty. That is, if its chessboard distance to an empty entry is bigger than k, and
+/// the accumulation is that distance minus one. [get_accumulation] keeps decornering while the last pass left
+/// two entries next to each other on a row, which tells how many passes are run, capped the same way.
+///
+pub fn get_accumulation_from_distance(input_data: &Vmatrix<u32>) -> Vmatrix<u32> {
+    let distances = integer_distance_transform(input_data, DistanceMetric::CHESSBOARD);
+
+    let mut passes: u32 = 1;
+    for row in distances.rows() {
+        for pair in row.windows(2) {
+            passes = passes.max(pair[0].min(pair[1]));
+        }
+    }
+    passes = passes.min(MAXIMUM_REDUCTIONS_DECORNERING - 1);
+
+    distances.map(|distance| distance.saturating_sub(1).min(passes))
+}
+
 /// Remove any entry in the sample data that is not surrounded by data too.
 ///
 pub fn decorner_once(input_data: &Vmatrix<u32>, two_points_in_row: &mut bool) -> Vmatrix<u32> {
@@ -84,15 +103,41 @@ pub fn decorner_once(input_data: &Vmatrix<u32>, two_points_in_row: &mut bool) ->
     result
 }
 
+/// See [decorner_once]. Decorner packed data, keeping an entry only if the copies of the data moved onto it from
+/// every neighbour are all set, a whole word at a time. The border is removed the same way.
+///
+pub fn decorner_once_bits(input_data: &BitMatrix, two_points_in_row: &mut bool) -> BitMatrix {
+    let size = input_data.size;
+    let mut result = BitMatrix::new(size);
+
+    *two_points_in_row = false;
+    if size < 3 {
+        return result;
+    }
+
+    for y in 1..(size - 1) {
+        result.fill(1 + y * size, (size - 1) + y * size, true);
+    }
+
+    let row_offset = size as isize;
+    for offset in [-row_offset - 1, -row_offset, -row_offset + 1, -1, 0, 1, row_offset - 1, row_offset, row_offset + 1] {
+        result = result.and(&input_data.shifted(offset)).expect("Shifted data shares the size of the input");
+    }
+
+    // The border columns are empty, so moving by one never joins the end of a row with the next one
+    *two_points_in_row = result.and(&result.shifted(1)).expect("Shifted data shares the size of the input").count_ones() > 0;
+
+    result
+}
+
 /// See [decorner_once]. As data on the first and last entry cannot be completelly surrounded by data,
 /// the result data is already set to zero on these rows.
 ///
 pub fn set_bound_rows_to_zero(input_data: &mut Vmatrix<u32>) {
     let input_size: usize = input_data.size;
-    let last_entry: usize = input_size * input_size;
-    for i in 0..input_size {
-        input_data.data[i] = 0;
-        input_data.data[last_entry - 1 - i] = 0;
+    for x in 0..input_size {
+        input_data[(x, 0)] = 0;
+        input_data[(x, input_size - 1)] = 0;
     }
 }
 
@@ -2,6 +2,51 @@ use crate::Vmatrix;
 use crate::Trigonometric;
 
 use crate::GlobalCurveData;
+use crate::naudr::distance::*;
+use crate::naudr::vectorise::curve_points;
+
+/// Shape walked around every entry by [write_bloats_with_shape]. The radius of an entry is the biggest shape of
+/// that kind centred on it that only covers data.
+///
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum BloatShape {
+    /// Square aligned with the rows, the original bloat, following [DistanceMetric::CHESSBOARD]
+    ///
+    SQUARE,
+    /// Square standing on a corner, following [DistanceMetric::CITYBLOCK]
+    ///
+    DIAMOND,
+    /// Round shape, following [DistanceMetric::EUCLIDEAN]. Closer to the width of strokes drawn with a round brush,
+    /// which a square overestimates on diagonals.
+    ///
+    DISC,
+}
+
+impl BloatShape {
+    pub fn metric(&self) -> DistanceMetric {
+        match self {
+            BloatShape::SQUARE => DistanceMetric::CHESSBOARD,
+            BloatShape::DIAMOND => DistanceMetric::CITYBLOCK,
+            BloatShape::DISC => DistanceMetric::EUCLIDEAN,
+        }
+    }
+
+    /// Whether an entry at the given offset from the centre is covered by the shape of the given radius. A radius
+    /// of 1 only covers the centre.
+    ///
+    pub fn covers(&self, offset_x: usize, offset_y: usize, radius: u32) -> bool {
+        if radius == 0 {
+            return false;
+        }
+
+        let reach = radius as usize - 1;
+        match self {
+            BloatShape::SQUARE => offset_x.max(offset_y) <= reach,
+            BloatShape::DIAMOND => offset_x + offset_y <= reach,
+            BloatShape::DISC => offset_x * offset_x + offset_y * offset_y <= reach * reach,
+        }
+    }
+}
 
 // First step is skippable, the bload data can just copy the initial input
 /// Write on the global output the minimal points on the set that contain the rest of the data. A point
@@ -9,51 +54,150 @@ use crate::GlobalCurveData;
 /// radius you can go from a single point and walk a full square while always having data on the path is the
 /// value that entry gets.
 ///
+/// The radius is the chessboard distance of the entry to the closest empty one, taking the border of the matrix
+/// as empty, so it comes from [distance_transform] in linear time instead of walking every square with [trace_at].
+/// Afterwards, from the biggest radius down, every entry kept clears the smaller radii within its square, which
+/// costs the area of the square for each of them.
+///
+/// Every entry within the square of a radius has data, so the squares rebuilt by [reconstruct_from_bloats] never
+/// cover an empty entry. [trace_at] only checks the outermost ring of each square instead, so it steps over
+/// empty entries inside it, and data with holes gets different radii than it used to. A 9x9 block with a single
+/// hole next to its centre was a single radius of 5 on the centre, covering the hole, and is now the radii of
+/// 2 and 3 that fit around it.
+///
 /// # Testing
 ///
 /// Running a test of the crate generates a file called "bloating.txt" from the "samplekanji.txt" to show
 /// an example of the operation.
 ///
 pub fn write_bloats(global_data: &mut GlobalCurveData, input_data: &Vmatrix<u32>) {
+    write_bloats_with_shape(global_data, input_data, BloatShape::SQUARE);
+}
+
+/// See [write_bloats]. Walk the given shape around every entry instead of a square, so the radius follows the
+/// metric of the shape.
+///
+pub fn write_bloats_with_shape(global_data: &mut GlobalCurveData, input_data: &Vmatrix<u32>, shape: BloatShape) {
     let row_size = global_data.row_size;
-    let data_size = row_size * row_size;
-    
-    let mut bloat_level: u32 = 1;
     let maximum_bloat: u32 = (row_size / 2) as u32;
+    if maximum_bloat < 2 {
+        return;
+    }
+
+    let mut interior: Vmatrix<u32> = input_data.normal_copy();
+    for i in 0..interior.data.len() {
+        if interior.test_border_index(i) {
+            interior.data[i] = 0;
+        }
+    }
+    let distances = distance_transform(&interior, shape.metric());
+
+    let result_array = &mut global_data.curves_global_output.data;
+    let mut by_level: Vec<Vec<usize>> = vec![Vec::new(); maximum_bloat as usize];
+    for (i, result) in result_array.iter_mut().enumerate() {
+        if input_data.data[i] != 1 || distances.data[i] == 0.0 {
+            continue;
+        }
+
+        // Every entry closer than the distance has data, the shape reaches up to the one just before it
+        let bloat_level = (distances.data[i].ceil() as u32).min(maximum_bloat - 1);
+        *result = bloat_level;
+        by_level[bloat_level as usize].push(i);
+    }
 
-    let mut data_array = &input_data.data;
-    let mut result_array = &mut global_data.curves_global_output.data;
-    let mut bloat_increased = true;
-    while bloat_level < maximum_bloat && bloat_increased {
-        bloat_increased = false;
-        for i in 0..data_size {
-            if input_data.test_border_index(i) {
+    // From the biggest radius down, the shapes of the entries left hide the smaller ones in them
+    for bloat_level in (1..maximum_bloat).rev() {
+        let reach = bloat_level as usize - 1;
+        for &i in &by_level[bloat_level as usize] {
+            if result_array[i] != bloat_level {
                 continue;
             }
-            if data_array[i] == 1 && result_array[i] != bloat_level {
-                if trace_at(input_data, bloat_level, row_size, i) {
-                    result_array[i] = bloat_level;
-                    bloat_increased = true;
+
+            let (x, y) = input_data.coordinates_of(i);
+            for square_y in (y - reach)..=(y + reach) {
+                for square_x in (x - reach)..=(x + reach) {
+                    let index = square_x + square_y * row_size;
+                    if shape.covers(square_x.abs_diff(x), square_y.abs_diff(y), bloat_level) && result_array[index] < bloat_level {
+                        result_array[index] = 0;
+                    }
                 }
             }
         }
-        bloat_level += 1;
     }
+}
 
-    while bloat_level > 0 {
-        for i in 0..data_size {
-            if input_data.test_border_index(i) {
-                continue;
+/// Estimate the width of every curve on the global data as (curve, width), ordered by curve. Curves are
+/// usually drawn along the middle of their stroke, and an entry there at a distance d from the closest empty
+/// entry sits on a stroke of width 2d - 1, so the width comes from the distances at the entries of the curve that
+/// are further from the empty ones than their neighbours.
+///
+pub fn stroke_widths(global_data: &GlobalCurveData, input_data: &Vmatrix<u32>, shape: BloatShape) -> Vec<(u32, f64)> {
+    let distances = distance_transform(input_data, shape.metric());
+
+    let mut result: Vec<(u32, f64)> = Vec::new();
+    for (curve, points) in curve_points(global_data) {
+        let mut ridge_total = 0.0;
+        let mut ridge_count = 0;
+        let mut widest = 0.0f64;
+
+        for point in points {
+            let index = point.x as usize + point.y as usize * input_data.size;
+            let distance = distances.data[index];
+            widest = widest.max(distance);
+
+            if distances.neighbours(index).all(|neighbour| distances.data[neighbour] <= distance) {
+                ridge_total += distance;
+                ridge_count += 1;
             }
-            if data_array[i] == 1 && result_array[i] == bloat_level {
-                let bloat_limit = bloat_level + 1;
-                for j in 1..bloat_limit {
-                    clean_at(input_data, bloat_level, j, row_size, i, result_array);
+        }
+
+        let middle = if ridge_count > 0 { ridge_total / ridge_count as f64 } else { widest };
+        result.push((curve, (2.0 * middle - 1.0).max(0.0)));
+    }
+
+    result
+}
+
+/// Get the entries left by [write_bloats] on the global output as (index, radius) pairs, ordered by index. This is
+/// a compact description of the data, as every pair stands for the square of side 2 * radius - 1 around it.
+///
+pub fn bloat_disks(global_data: &GlobalCurveData) -> Vec<(usize, u32)> {
+    global_data.curves_global_output.data.iter().enumerate()
+        .filter(|(_, radius)| **radius != 0)
+        .map(|(index, radius)| (index, *radius))
+        .collect()
+}
+
+/// Inverse of [bloat_disks]. Get a matrix with 1 on every entry covered by the square of any of the pairs, and 0
+/// elsewhere. Compare it with the original data to know what the description lost, as bloats never reach the
+/// border of the matrix or thin lines.
+///
+pub fn reconstruct_from_bloats(disks: &[(usize, u32)], row_size: usize) -> Vmatrix<u32> {
+    reconstruct_from_bloats_with_shape(disks, row_size, BloatShape::SQUARE)
+}
+
+/// See [reconstruct_from_bloats]. Rebuild bloats written by [write_bloats_with_shape] with the same shape.
+///
+pub fn reconstruct_from_bloats_with_shape(disks: &[(usize, u32)], row_size: usize, shape: BloatShape) -> Vmatrix<u32> {
+    let mut result: Vmatrix<u32> = Vmatrix::<u32>::initialize(row_size, 0);
+
+    for &(index, radius) in disks {
+        if radius == 0 {
+            continue;
+        }
+
+        let (x, y) = result.coordinates_of(index);
+        let reach = radius as usize - 1;
+        for square_y in y.saturating_sub(reach)..=(y + reach).min(row_size - 1) {
+            for square_x in x.saturating_sub(reach)..=(x + reach).min(row_size - 1) {
+                if shape.covers(square_x.abs_diff(x), square_y.abs_diff(y), radius) {
+                    result[(square_x, square_y)] = 1;
                 }
             }
         }
-        bloat_level -= 1;
     }
+
+    result
 }
 
 /// Given a point that already has a radius assigned, this method allows to eliminate entries at a smaller
@@ -1,5 +1,6 @@
 use crate::Vmatrix;
-use crate::Trigonometric;
+use crate::Axis;
+use crate::Cardinal;
 use crate::Vector2;
 
 use crate::get_index_distance;
@@ -46,12 +47,24 @@ pub struct GlobalCurveData {
     /// Each new point within the current curve is ordered according to this increasing value
     ///
     pub global_orderd_cardin: u32,
+
+    /// How both matrices are stored, compared to the data the curves are found on. Along columns every point is
+    /// written transposed, as if [GlobalCurveData::transpose_internal] had been called before writing it. See
+    /// [GlobalCurveData::to_rows] to get them back along the rows.
+    ///
+    pub axis: Axis,
 }
 
 impl GlobalCurveData {
     /// Set the initial values for a GlobalCurveData that will be shared throughout the operation
     ///
     pub fn new(size: usize) -> GlobalCurveData {
+        GlobalCurveData::new_along(size, Axis::ROWS)
+    }
+
+    /// See [GlobalCurveData::new]. Store the curves along the given axis, see [GlobalCurveData::axis].
+    ///
+    pub fn new_along(size: usize, axis: Axis) -> GlobalCurveData {
         GlobalCurveData {
             row_size: size,
 
@@ -60,16 +73,35 @@ impl GlobalCurveData {
 
             global_output_number: 1,
             global_orderd_cardin: 0,
+
+            axis,
         }
     }
 
     /// Transpose both internal matrices for this instance of GlobalCurveData, allowing to operate
     /// against fixed data that couldn't be transposed
     ///
+    /// The axis is kept as it is, since the data given afterwards is expected to be transposed too. Use
+    /// [GlobalCurveData::to_rows] to read the curves on the frame of the data instead.
+    ///
     pub fn transpose_internal(&mut self) {
         self.curves_global_output.transpose();
         self.curves_global_orderd.transpose();
     }
+
+    /// Store both internal matrices along the rows, so every point is on the same entry it has on the data the
+    /// curves were found on, whatever the axis they were stored along. Nothing changes if they already are.
+    ///
+    pub fn to_rows(&mut self) {
+        let axis = self.axis;
+        let size = self.row_size;
+        for stored in [&mut self.curves_global_output, &mut self.curves_global_orderd] {
+            let data = (0..stored.data.len()).map(|index| stored.data[axis.index_on(index, size)]).collect();
+            *stored = Vmatrix::build(size, data);
+        }
+
+        self.axis = Axis::ROWS;
+    }
 }
 
 /// On a data set, find all the closed bodies that represent a curve, when the vector is represented
@@ -81,19 +113,28 @@ impl GlobalCurveData {
 /// data where there were long rows on one hand, and long columns on the other hand, basically removing
 /// any lines except those that had a slope != 0
 pub fn get_curves(global_data: &mut GlobalCurveData, input_data: &Vmatrix<u32>) -> Vmatrix<u32> {
+    get_curves_along(global_data, input_data, Axis::ROWS)
+}
+
+/// See [get_curves]. Walk the data along the given axis, giving the same result as transposing the data, looking
+/// for curves on it and transposing the result back, without copying anything.
+///
+pub fn get_curves_along(global_data: &mut GlobalCurveData, input_data: &Vmatrix<u32>, axis: Axis) -> Vmatrix<u32> {
     let set_length = input_data.data.len();
+    let row_size = global_data.row_size;
     
-    let mut result_set = Vmatrix::<u32>::initialize(global_data.row_size, 0);
+    let mut result_set = Vmatrix::<u32>::initialize(row_size, 0);
 
     let working_input = &input_data.data;
 
     let mut curve_count = 0;
 
     for i in 0..set_length {
-        if working_input[i] == 1 && result_set.data[i] == 0 {
-            result_set.data[i] = 2;
-            draw_curve_on(&input_data, &mut result_set, &global_data, i);
-            hollow_set(2, 1, global_data.row_size, &input_data, &mut result_set);
+        let at = axis.index_on(i, row_size);
+        if working_input[at] == 1 && result_set.data[at] == 0 {
+            result_set.data[at] = 2;
+            draw_curve_on(input_data, &mut result_set, i, axis);
+            hollow_set(2, 1, row_size, input_data, &mut result_set, axis);
             curve_count += 1;
         } 
     }
@@ -103,30 +144,28 @@ pub fn get_curves(global_data: &mut GlobalCurveData, input_data: &Vmatrix<u32>)
 
 /// Mark values that have been processed already two prevent drawing the curves out of them on loop
 ///
-fn hollow_set(anchor_value: u32, hollow_value: u32, row_size: usize, input_data: &Vmatrix<u32>, result_set: &mut Vmatrix<u32>) {
+fn hollow_set(anchor_value: u32, hollow_value: u32, row_size: usize, input_data: &Vmatrix<u32>, result_set: &mut Vmatrix<u32>, axis: Axis) {
     let set_size = result_set.data.len();
 
-    let mut row_count = 0;
     let mut anchor_enabled: bool = false;
 
     let working_input = &input_data.data;
     let working_result = &mut result_set.data;
 
     for i in 0..set_size {
-        if anchor_enabled && (working_input[i] != 0) && (working_result[i] != anchor_value) {
-            working_result[i] = hollow_value;
+        let at = axis.index_on(i, row_size);
+        if anchor_enabled && (working_input[at] != 0) && (working_result[at] != anchor_value) {
+            working_result[at] = hollow_value;
         }
-        if anchor_enabled && working_input[i] == 0 {
+        if anchor_enabled && working_input[at] == 0 {
             anchor_enabled = !anchor_enabled;
         }
-        if working_result[i] == anchor_value {
+        if working_result[at] == anchor_value {
             anchor_enabled = true;
         }
 
-        row_count += 1;
-        if row_count >= row_size {
+        if input_data.coordinates_of(i).0 == row_size - 1 {
             anchor_enabled = false;
-            row_count = 0;
         }
     }
 }
@@ -141,8 +180,8 @@ fn hollow_set(anchor_value: u32, hollow_value: u32, row_size: usize, input_data:
 /// data like that provided by "samplekanji.txt". You should see any line that isn't completely straigth, surrounded
 /// by "2", and with "1" in the interior. Also, the resulting curves will take up less space than the original.
 /// Straight lines are treated separately.
-fn draw_curve_on(input_data: &Vmatrix<u32>, result_output: &mut Vmatrix<u32>, global_data: &GlobalCurveData, index: usize) {
-    let mut current_direction = Trigonometric::COS;
+fn draw_curve_on(input_data: &Vmatrix<u32>, result_output: &mut Vmatrix<u32>, index: usize, axis: Axis) {
+    let mut current_direction = Cardinal::E;
     let mut current_index = index;
     let mut set_length = input_data.data.len();
     let mut number_of_checks = 0;
@@ -166,7 +205,7 @@ fn draw_curve_on(input_data: &Vmatrix<u32>, result_output: &mut Vmatrix<u32>, gl
 
         last_index_in_loop = current_index as i32;
 
-        index_natural_direction = paint_on_direction(current_index, global_data.row_size, &current_direction, 0, input_data, result_output);
+        index_natural_direction = paint_on_direction(current_index, &current_direction, input_data, result_output, axis);
         if index_natural_direction != current_index {
             current_index = index_natural_direction;
             cardinal_changes = 0;
@@ -180,7 +219,7 @@ fn draw_curve_on(input_data: &Vmatrix<u32>, result_output: &mut Vmatrix<u32>, gl
             continue;
         }
 
-        index_45_degrees = paint_on_direction(current_index, global_data.row_size, &current_direction, -1, input_data, result_output);
+        index_45_degrees = paint_on_direction(current_index, &current_direction.rotate(1), input_data, result_output, axis);
         if index_45_degrees != current_index {
             current_index = index_45_degrees;
             cardinal_changes = 0;
@@ -194,7 +233,7 @@ fn draw_curve_on(input_data: &Vmatrix<u32>, result_output: &mut Vmatrix<u32>, gl
             continue;
         }
 
-        index_overdue_direction = paint_on_direction(current_index, global_data.row_size, &Trigonometric::derivative(&current_direction), 0, input_data, result_output);
+        index_overdue_direction = paint_on_direction(current_index, &current_direction.rotate(2), input_data, result_output, axis);
         if index_overdue_direction != current_index {
             current_index = index_overdue_direction;
             cardinal_changes = 0;
@@ -208,25 +247,23 @@ fn draw_curve_on(input_data: &Vmatrix<u32>, result_output: &mut Vmatrix<u32>, gl
             continue;
         }
 
-        current_direction = Trigonometric::derivative(&current_direction);
+        current_direction = current_direction.rotate(2);
         cardinal_changes += 1;
 
         number_of_checks += 1;
         if number_of_checks >= maximum_checks {
-            result_output.data[index] = 6;
-            result_output.write_to_file(String::from("debug.txt"));
             panic!("The process gave up before checking all values. Is MAX_CHECKS_FACTOR too low? Index calling: {}", index)
         }
     }
 }
 
-fn paint_on_direction(from_index: usize, row_size: usize, direction: &Trigonometric, offset: i32, input_data: &Vmatrix<u32>, result_output: &mut Vmatrix<u32>) -> usize {
+fn paint_on_direction(from_index: usize, direction: &Cardinal, input_data: &Vmatrix<u32>, result_output: &mut Vmatrix<u32>, axis: Axis) -> usize {
     let mut new_index = from_index;
 
-    let result_direction = Trigonometric::get_index_from_direction(from_index, row_size, &direction, offset);
-    if input_data.test_index(result_direction) {
-        if input_data.data[result_direction] == 1 && result_output.data[result_direction] != 1 {
-            result_output.data[result_direction] = 2;
+    if let Some(result_direction) = input_data.neighbour(from_index, direction) {
+        let at = axis.index_on(result_direction, input_data.size);
+        if input_data.data[at] == 1 && result_output.data[at] != 1 {
+            result_output.data[at] = 2;
             new_index = result_direction;
         }
     }
@@ -234,7 +271,7 @@ fn paint_on_direction(from_index: usize, row_size: usize, direction: &Trigonomet
     return new_index
 }
 
-fn get_smooth_curves(input_data: &Vmatrix<u32>, from_point: usize, to_point: usize, row_size: usize) -> Vec<usize> {
+fn get_smooth_curves(input_data: &Vmatrix<u32>, from_point: usize, to_point: usize, row_size: usize, axis: Axis) -> Vec<usize> {
     let mut result_list: Vec<usize> = vec![];
 
     let delta1: &mut Vector2<i32> = &mut Vector2::new(0, 0);
@@ -248,7 +285,7 @@ fn get_smooth_curves(input_data: &Vmatrix<u32>, from_point: usize, to_point: usi
     let working_data = &input_data.data;
 
     // backup was originally used here
-    if working_data[middle_point] == 1 {
+    if working_data[axis.index_on(middle_point, row_size)] == 1 {
         result_list.push(middle_point);
     }
     else {
@@ -262,10 +299,10 @@ fn get_smooth_curves(input_data: &Vmatrix<u32>, from_point: usize, to_point: usi
 
         // Another backup set
         if input_data.test_index(middle_point_orth1 as usize) {
-            result_list.append(&mut get_smooth_curves(&input_data, middle_point, middle_point_orth1 as usize, row_size));
+            result_list.append(&mut get_smooth_curves(input_data, middle_point, middle_point_orth1 as usize, row_size, axis));
         }
         if input_data.test_index(middle_point_orth2 as usize) {
-            result_list.append(&mut get_smooth_curves(&input_data, middle_point, middle_point_orth2 as usize, row_size));
+            result_list.append(&mut get_smooth_curves(input_data, middle_point, middle_point_orth2 as usize, row_size, axis));
         }
     }
 
@@ -278,6 +315,13 @@ fn get_smooth_curves(input_data: &Vmatrix<u32>, from_point: usize, to_point: usi
 /// definitions for every type of curve on a dataset
 ///
 pub fn mark_curve_points<'a>(input_data: &Vmatrix<u32>, result_set: &'a mut Vmatrix<u32>, global_data: &mut GlobalCurveData, dominant_curve: bool) -> &'a mut Vmatrix<u32> {
+    mark_curve_points_along(input_data, result_set, global_data, dominant_curve, Axis::ROWS)
+}
+
+/// See [mark_curve_points]. Walk the curves found by [get_curves_along] along the same axis. Points are written
+/// on the global data as stored along [GlobalCurveData::axis].
+///
+pub fn mark_curve_points_along<'a>(input_data: &Vmatrix<u32>, result_set: &'a mut Vmatrix<u32>, global_data: &mut GlobalCurveData, dominant_curve: bool, axis: Axis) -> &'a mut Vmatrix<u32> {
     // We used to make a backup of the input here on C#, but it might be unnecesarry, if input is modified
     // throughout this, create the backup
 
@@ -291,42 +335,43 @@ pub fn mark_curve_points<'a>(input_data: &Vmatrix<u32>, result_set: &'a mut Vmat
     for i in 0..set_length {
         global_data.global_orderd_cardin = 0;
 
-        if working_input[i] == 2 && result_set.data[i] == 0 {
-            result_set.data[i] = 3;
-            returning_index = find_curve_on(&input_data, result_set, global_data, i);
+        let at = axis.index_on(i, row_size);
+        if working_input[at] == 2 && result_set.data[at] == 0 {
+            result_set.data[at] = 3;
+            returning_index = find_curve_on_along(input_data, result_set, global_data, i, axis);
 
             if row_distance(i as i32, returning_index as i32, row_size) == 0 && !dominant_curve {
                 for x in i..=returning_index {
-                    result_set.data[x] = 1;
+                    result_set.data[axis.index_on(x, row_size)] = 1;
                 }
 
                 continue;
             }
 
             if returning_index == i {
-                result_set.data[i] = 1;
+                result_set.data[at] = 1;
             }
             else {
-                let smooth_curve_points = get_smooth_curves(&input_data, i, returning_index, row_size);
+                let smooth_curve_points = get_smooth_curves(input_data, i, returning_index, row_size, axis);
 
                 if smooth_curve_points.len() == 0 {
                      continue;
                 }
 
                 if !dominant_curve {
-                    write_to_global(global_data, i);
+                    write_to_global(global_data, i, axis);
                     for entry in smooth_curve_points {
-                        write_to_global(global_data, entry);
-                        result_set.data[entry] = 3;
+                        write_to_global(global_data, entry, axis);
+                        result_set.data[axis.index_on(entry, row_size)] = 3;
                     }
-                    write_to_global(global_data, returning_index);
+                    write_to_global(global_data, returning_index, axis);
                 }
                 else {
                     let middle_point_entry = smooth_curve_points[0];
 
-                    result_set.data[i] = 1;
-                    result_set.data[returning_index] = 1;
-                    result_set.data[middle_point_entry] = 0;
+                    result_set.data[at] = 1;
+                    result_set.data[axis.index_on(returning_index, row_size)] = 1;
+                    result_set.data[axis.index_on(middle_point_entry, row_size)] = 0;
 
                     let xdelta1 = column_distance(i as i32, middle_point_entry as i32, row_size);
                     let xdelta2 = column_distance(returning_index as i32, middle_point_entry as i32, row_size);
@@ -334,10 +379,10 @@ pub fn mark_curve_points<'a>(input_data: &Vmatrix<u32>, result_set: &'a mut Vmat
                     let final_point1 = middle_point_entry as i32 + xdelta1;
                     let final_point2 = middle_point_entry as i32 + xdelta2;
 
-                    result_set.data[final_point1 as usize] = 3;
-                    write_to_global(global_data, final_point1 as usize);
-                    result_set.data[final_point2 as usize] = 3;
-                    write_to_global(global_data, final_point2 as usize);
+                    result_set.data[axis.index_on(final_point1 as usize, row_size)] = 3;
+                    write_to_global(global_data, final_point1 as usize, axis);
+                    result_set.data[axis.index_on(final_point2 as usize, row_size)] = 3;
+                    write_to_global(global_data, final_point2 as usize, axis);
                 }
 
                 global_data.global_output_number += 1;
@@ -357,30 +402,32 @@ pub fn mark_curve_points<'a>(input_data: &Vmatrix<u32>, result_set: &'a mut Vmat
 /// unless there happens to be a point defined at the last column of the data. Also, this situation should
 /// have been prevented when calling [decorner_once] from the accumulate module, as values on the borders
 /// of the matrix aren't ever kept, being impossible for them to be surrounded by data.
-fn write_to_global(global_data: &mut GlobalCurveData, at_index: usize) {
+fn write_to_global(global_data: &mut GlobalCurveData, at_index: usize, axis: Axis) {
+    let row_size = global_data.row_size;
+    let stored_at = global_data.axis.index_on(axis.index_on(at_index, row_size), row_size);
+
     let curves_output = &mut global_data.curves_global_output.data;
     let curves_orderd = &mut global_data.curves_global_orderd.data;
 
-    let value_at_global = curves_output[at_index];
+    let value_at_global = curves_output[stored_at];
     if value_at_global != 0 {
-        write_to_global(global_data, at_index + 1);
+        write_to_global(global_data, at_index + 1, axis);
         return;
     }
 
-    curves_output[at_index] = global_data.global_output_number;
-    curves_orderd[at_index] = global_data.global_orderd_cardin;
+    curves_output[stored_at] = global_data.global_output_number;
+    curves_orderd[stored_at] = global_data.global_orderd_cardin;
     global_data.global_orderd_cardin += 1;
 }
 
-fn get_if_curve_value (input_data: &Vmatrix<u32>, result_output: &mut Vmatrix<u32>, from_index: usize, global_data: &GlobalCurveData, direction: &Trigonometric, offset: i32) -> usize {
+fn get_if_curve_value (input_data: &Vmatrix<u32>, result_output: &mut Vmatrix<u32>, from_index: usize, direction: &Cardinal, axis: Axis) -> usize {
     let mut new_index = from_index;
-    let row_size = global_data.row_size;
 
-    let result_direction = Trigonometric::get_index_from_direction(from_index, row_size, &direction, offset);
-    if input_data.test_index(result_direction) {
-        if input_data.data[result_direction] == 2 {
-            if result_output.data[result_direction] != 3 {
-                result_output.data[result_direction] = 1;
+    if let Some(result_direction) = input_data.neighbour(from_index, direction) {
+        let at = axis.index_on(result_direction, input_data.size);
+        if input_data.data[at] == 2 {
+            if result_output.data[at] != 3 {
+                result_output.data[at] = 1;
             }
             new_index = result_direction;
         }
@@ -393,11 +440,17 @@ fn get_if_curve_value (input_data: &Vmatrix<u32>, result_output: &mut Vmatrix<u3
 // ??? result_output was passed as a mutable reference, but then goes directly into get_if_curve_value ??
 // Check if the data even changes, cause this can be non-intentional
 pub fn find_curve_on(input_data: &Vmatrix<u32>, result_output: &mut Vmatrix<u32>, global_data: &GlobalCurveData, index: usize) -> usize{
+    find_curve_on_along(input_data, result_output, global_data, index, Axis::ROWS)
+}
+
+/// See [find_curve_on]. The index is a position when walking the data along the given axis, and so is the result.
+///
+pub fn find_curve_on_along(input_data: &Vmatrix<u32>, result_output: &mut Vmatrix<u32>, global_data: &GlobalCurveData, index: usize, axis: Axis) -> usize {
     // Should have a working result_set by this point - on C# we used to call a "TestOrInitialize"
 
     let row_size = global_data.row_size;
 
-    let mut current_direction = Trigonometric::COS;
+    let mut current_direction = Cardinal::E;
     let mut current_index = index;
     let mut set_length = input_data.data.len();
     let mut number_of_checks = 0;
@@ -421,7 +474,7 @@ pub fn find_curve_on(input_data: &Vmatrix<u32>, result_output: &mut Vmatrix<u32>
 
         last_index_in_loop = current_index as i32;
 
-        index_natural_direction = get_if_curve_value(&input_data, result_output, current_index, global_data, &current_direction, 0);
+        index_natural_direction = get_if_curve_value(input_data, result_output, current_index, &current_direction, axis);
         if index_natural_direction != current_index {
             current_index = index_natural_direction;
             cardinal_changes = 0;
@@ -435,14 +488,14 @@ pub fn find_curve_on(input_data: &Vmatrix<u32>, result_output: &mut Vmatrix<u32>
             }
 
             if index_natural_direction == index {
-                result_output.data[index_of_best_distance] = 3;
+                result_output.data[axis.index_on(index_of_best_distance, row_size)] = 3;
                 return index_of_best_distance;
             }
 
             continue;
         }
 
-        index_45_degrees = get_if_curve_value(&input_data, result_output, current_index, global_data, &current_direction, -1);
+        index_45_degrees = get_if_curve_value(input_data, result_output, current_index, &current_direction.rotate(1), axis);
         if index_45_degrees != current_index {
             current_index = index_45_degrees;
             cardinal_changes = 0;
@@ -456,14 +509,14 @@ pub fn find_curve_on(input_data: &Vmatrix<u32>, result_output: &mut Vmatrix<u32>
             }
 
             if index_45_degrees == index {
-                result_output.data[index_of_best_distance] = 3;
+                result_output.data[axis.index_on(index_of_best_distance, row_size)] = 3;
                 return index_of_best_distance;
             }
 
             continue;
         }
 
-        index_overdue_direction = get_if_curve_value(&input_data, result_output, current_index, global_data, &Trigonometric::derivative(&current_direction), 0);
+        index_overdue_direction = get_if_curve_value(input_data, result_output, current_index, &current_direction.rotate(2), axis);
         if index_overdue_direction != current_index {
             current_index = index_overdue_direction;
             cardinal_changes = 0;
@@ -477,14 +530,14 @@ pub fn find_curve_on(input_data: &Vmatrix<u32>, result_output: &mut Vmatrix<u32>
             }
 
             if index_overdue_direction == index {
-                result_output.data[index_of_best_distance] = 3;
+                result_output.data[axis.index_on(index_of_best_distance, row_size)] = 3;
                 return index_of_best_distance;
             }
 
             continue;
         }
 
-        current_direction = Trigonometric::derivative(&current_direction);
+        current_direction = current_direction.rotate(2);
         cardinal_changes += 1;
 
         number_of_checks += 1;
@@ -0,0 +1,203 @@
+use crate::Vmatrix;
+use crate::BitMatrix;
+use crate::Vector2;
+
+/// Which entries are considered to be touching each other when looking for bodies on a matrix
+///
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum Connectivity {
+    /// Only entries sharing a side, this is on the same row or column
+    ///
+    FOUR,
+    /// Entries sharing a side or a corner
+    ///
+    EIGHT,
+}
+
+/// A single body found by [label_components]
+///
+#[derive(Clone, Debug)]
+pub struct Component {
+    /// Value the entries of this body get on the label matrix
+    ///
+    pub label: u32,
+    /// Number of entries in the body
+    ///
+    pub area: usize,
+
+    /// Top left corner of the box containing the body, as (column, row)
+    ///
+    pub bounding_min: Vector2<usize>,
+    /// Bottom right corner of the box containing the body, as (column, row)
+    ///
+    pub bounding_max: Vector2<usize>,
+
+    /// Average of the coordinates of every entry in the body
+    ///
+    pub centroid: Vector2<f64>,
+}
+
+/// Result of [label_components]
+///
+pub struct ComponentLabels {
+    /// Every entry holds the label of the body it belongs to, or 0 if it had no data
+    ///
+    pub labels: Vmatrix<u32>,
+    /// The bodies found, where the body with label n is at position n - 1
+    ///
+    pub components: Vec<Component>,
+}
+
+/// Find the separate bodies of data on a matrix, where any entry different from zero is data. Bodies are labelled
+/// from 1, in the order their first entry appears when reading the data row by row.
+///
+pub fn label_components(input_data: &Vmatrix<u32>, connectivity: Connectivity) -> ComponentLabels {
+    let mut labels: Vmatrix<u32> = Vmatrix::<u32>::initialize(input_data.size, 0);
+    let mut components: Vec<Component> = Vec::new();
+
+    let mut pending: Vec<usize> = Vec::new();
+    for start in 0..input_data.data.len() {
+        if input_data.data[start] == 0 || labels.data[start] != 0 {
+            continue;
+        }
+
+        let label = components.len() as u32 + 1;
+        let (start_x, start_y) = input_data.coordinates_of(start);
+        let mut component = Component {
+            label,
+            area: 0,
+            bounding_min: Vector2::new(start_x, start_y),
+            bounding_max: Vector2::new(start_x, start_y),
+            centroid: Vector2::new(0.0, 0.0),
+        };
+
+        labels.data[start] = label;
+        pending.push(start);
+        while let Some(index) = pending.pop() {
+            let (x, y) = input_data.coordinates_of(index);
+            component.area += 1;
+            component.bounding_min = Vector2::new(component.bounding_min.x.min(x), component.bounding_min.y.min(y));
+            component.bounding_max = Vector2::new(component.bounding_max.x.max(x), component.bounding_max.y.max(y));
+            component.centroid = Vector2::new(component.centroid.x + x as f64, component.centroid.y + y as f64);
+
+            let neighbours: Vec<usize> = match connectivity {
+                Connectivity::FOUR => input_data.axis_neighbours(index).collect(),
+                Connectivity::EIGHT => input_data.neighbours(index).collect(),
+            };
+            for neighbour in neighbours {
+                if input_data.data[neighbour] != 0 && labels.data[neighbour] == 0 {
+                    labels.data[neighbour] = label;
+                    pending.push(neighbour);
+                }
+            }
+        }
+
+        let area = component.area as f64;
+        component.centroid = Vector2::new(component.centroid.x / area, component.centroid.y / area);
+        components.push(component);
+    }
+
+    ComponentLabels {
+        labels,
+        components,
+    }
+}
+
+/// See [label_components]. Label packed data, going over the runs of data on every row, found a whole word at a
+/// time, and joining the runs that touch a run on the row above. Gives the same labels and bodies.
+///
+pub fn label_components_bits(input_data: &BitMatrix, connectivity: Connectivity) -> ComponentLabels {
+    let size = input_data.size;
+    let reach = match connectivity {
+        Connectivity::FOUR => 0,
+        Connectivity::EIGHT => 1,
+    };
+
+    // Runs as (row, first column, last column), and the run each one is joined to
+    let mut runs: Vec<(usize, usize, usize)> = Vec::new();
+    let mut parents: Vec<usize> = Vec::new();
+
+    let mut previous_row = 0..0;
+    for y in 0..size {
+        let row = y * size;
+        let row_start = runs.len();
+
+        let mut position = row;
+        while let Some(start) = input_data.next_one(position, row + size) {
+            let end = input_data.next_zero(start, row + size);
+            let (first, last) = (start - row, end - 1 - row);
+
+            let current = runs.len();
+            runs.push((y, first, last));
+            parents.push(current);
+
+            for above in previous_row.clone() {
+                let (_, above_first, above_last) = runs[above];
+                if above_first <= last + reach && first <= above_last + reach {
+                    let current_root = find_root(&mut parents, current);
+                    let above_root = find_root(&mut parents, above);
+                    parents[current_root.max(above_root)] = current_root.min(above_root);
+                }
+            }
+
+            position = end;
+        }
+
+        previous_row = row_start..runs.len();
+    }
+
+    let mut labels: Vmatrix<u32> = Vmatrix::<u32>::initialize(size, 0);
+    let mut components: Vec<Component> = Vec::new();
+    let mut run_labels: Vec<u32> = vec![0; runs.len()];
+
+    for (current, (y, first, last)) in runs.iter().copied().enumerate() {
+        let root = find_root(&mut parents, current);
+        if run_labels[root] == 0 {
+            run_labels[root] = components.len() as u32 + 1;
+            components.push(Component {
+                label: run_labels[root],
+                area: 0,
+                bounding_min: Vector2::new(first, y),
+                bounding_max: Vector2::new(last, y),
+                centroid: Vector2::new(0.0, 0.0),
+            });
+        }
+
+        let label = run_labels[root];
+        let component = &mut components[label as usize - 1];
+        let length = last - first + 1;
+
+        component.area += length;
+        component.bounding_min = Vector2::new(component.bounding_min.x.min(first), component.bounding_min.y.min(y));
+        component.bounding_max = Vector2::new(component.bounding_max.x.max(last), component.bounding_max.y.max(y));
+        component.centroid = Vector2::new(
+            component.centroid.x + ((first + last) * length) as f64 / 2.0,
+            component.centroid.y + (y * length) as f64,
+        );
+
+        for entry in &mut labels.data[(first + y * size)..=(last + y * size)] {
+            *entry = label;
+        }
+    }
+
+    for component in components.iter_mut() {
+        let area = component.area as f64;
+        component.centroid = Vector2::new(component.centroid.x / area, component.centroid.y / area);
+    }
+
+    ComponentLabels {
+        labels,
+        components,
+    }
+}
+
+/// First run of the body the given run belongs to, shortening the way there for the next search
+///
+fn find_root(parents: &mut [usize], mut run: usize) -> usize {
+    while parents[run] != run {
+        parents[run] = parents[parents[run]];
+        run = parents[run];
+    }
+
+    run
+}
@@ -0,0 +1,164 @@
+use crate::Vmatrix;
+use crate::Vector2;
+use crate::Cardinal;
+
+/// Outlines found by [trace_contours]. The three vectors are parallel, so the outline at position n is a hole
+/// if is_hole[n] and is nested on the outline at parent[n].
+///
+pub struct Contours {
+    /// Every outline as the ordered list of the (column, row) entries on it. The last point connects back with the
+    /// first one.
+    ///
+    pub outlines: Vec<Vec<Vector2<i32>>>,
+    /// Whether the outline surrounds a hole within a body, instead of the body itself
+    ///
+    pub is_hole: Vec<bool>,
+    /// Position of the outline that directly contains this one, None for the outer outlines on the matrix
+    ///
+    pub parent: Vec<Option<usize>>,
+}
+
+/// Get the ordered outlines of every body on a matrix, and the holes within them, where any entry different
+/// from zero is data. Bodies are 8-connected. Outlines are listed in the order their first entry appears when
+/// reading the data row by row.
+///
+/// # Method
+///
+/// Border following as described by Suzuki and Abe (1985). The data is copied into a matrix with an extra empty
+/// frame, so walking around entries on the border of the input never leaves the data. Borders get numbered from
+/// 2, as 1 is reserved for the frame, and every entry on a border is marked with its number, negative when the
+/// entry on its right is empty, to know when a new border starts.
+///
+pub fn trace_contours(input_data: &Vmatrix<u32>) -> Contours {
+    let padded_size = input_data.size as i32 + 2;
+    let mut padded = vec![0i32; (padded_size * padded_size) as usize];
+    for (x, y, value) in input_data.iter_coords() {
+        if value != 0 {
+            padded[(x + 1) + (y + 1) * padded_size as usize] = 1;
+        }
+    }
+    let at = |x: i32, y: i32| (x + y * padded_size) as usize;
+
+    let mut result = Contours {
+        outlines: Vec::new(),
+        is_hole: Vec::new(),
+        parent: Vec::new(),
+    };
+
+    // Indexed by border number - 1, the frame being a hole with no parent
+    let mut border_is_hole: Vec<bool> = vec![true];
+    let mut border_parent: Vec<Option<i32>> = vec![None];
+    let mut border_number: i32 = 1;
+
+    for y in 1..(padded_size - 1) {
+        let mut last_border: i32 = 1;
+
+        for x in 1..(padded_size - 1) {
+            let value = padded[at(x, y)];
+            let mut start_from: Option<(i32, bool)> = None;
+
+            if value == 1 && padded[at(x - 1, y)] == 0 {
+                start_from = Some((Cardinal::W as i32, false));
+            }
+            else if value >= 1 && padded[at(x + 1, y)] == 0 {
+                if value > 1 {
+                    last_border = value;
+                }
+                start_from = Some((Cardinal::E as i32, true));
+            }
+
+            if let Some((start_direction, is_hole)) = start_from {
+                border_number += 1;
+
+                let last_index = (last_border - 1) as usize;
+                let parent = if is_hole != border_is_hole[last_index] {
+                    Some(last_border)
+                }
+                else {
+                    border_parent[last_index]
+                };
+
+                border_is_hole.push(is_hole);
+                border_parent.push(parent);
+
+                let outline = follow_border(&mut padded, padded_size, Vector2::new(x, y), start_direction, border_number);
+
+                result.outlines.push(outline);
+                result.is_hole.push(is_hole);
+                result.parent.push(match parent {
+                    Some(number) if number > 1 => Some((number - 2) as usize),
+                    _ => None,
+                });
+            }
+
+            let value = padded[at(x, y)];
+            if value != 0 && value != 1 {
+                last_border = value.abs();
+            }
+        }
+    }
+
+    result
+}
+
+/// See [trace_contours]. Walk a single border from its first entry, marking it on the padded data with its
+/// number, and return its entries without the padding.
+///
+fn follow_border(padded: &mut [i32], padded_size: i32, start: Vector2<i32>, start_direction: i32, border_number: i32) -> Vec<Vector2<i32>> {
+    let at = |point: Vector2<i32>| (point.x + point.y * padded_size) as usize;
+    let step = |point: Vector2<i32>, direction: i32| point + Cardinal::from_int(direction.rem_euclid(8) as usize).as_delta();
+    let direction_to = |from: Vector2<i32>, to: Vector2<i32>| {
+        (0..8).find(|direction| step(from, *direction) == to).unwrap_or(0)
+    };
+
+    let mut outline: Vec<Vector2<i32>> = Vec::new();
+    let offset = Vector2::new(1, 1);
+
+    // Clockwise from the empty entry that started the border, the first entry found is the last one on the walk
+    let last_on_walk = (0..8).map(|turn| start_direction + turn).find(|direction| padded[at(step(start, *direction))] != 0);
+    let last_on_walk = match last_on_walk {
+        Some(direction) => step(start, direction),
+        None => {
+            padded[at(start)] = -border_number;
+            outline.push(start - offset);
+            return outline;
+        }
+    };
+
+    let mut previous = last_on_walk;
+    let mut current = start;
+    loop {
+        // Counterclockwise from the previous entry, the first entry found is the next one on the walk
+        let previous_direction = direction_to(current, previous);
+        let mut right_was_empty = false;
+        let mut next_direction = previous_direction;
+        for turn in 1..=8 {
+            let direction = (previous_direction - turn).rem_euclid(8);
+            if padded[at(step(current, direction))] != 0 {
+                next_direction = direction;
+                break;
+            }
+            if direction == Cardinal::E as i32 {
+                right_was_empty = true;
+            }
+        }
+
+        if right_was_empty {
+            padded[at(current)] = -border_number;
+        }
+        else if padded[at(current)] == 1 {
+            padded[at(current)] = border_number;
+        }
+        outline.push(current - offset);
+
+        let next = step(current, next_direction);
+        if next == start && current == last_on_walk {
+            break;
+        }
+
+        previous = current;
+        current = next;
+    }
+
+    outline
+}
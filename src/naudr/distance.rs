@@ -0,0 +1,195 @@
+use crate::Vmatrix;
+
+/// How the distance between two entries of a matrix is measured
+///
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum DistanceMetric {
+    /// Biggest difference on a single axis, so all 8 neighbours are at 1
+    ///
+    CHESSBOARD,
+    /// Sum of the differences on both axes, so only the 4 neighbours on the same row or column are at 1
+    ///
+    CITYBLOCK,
+    /// Straight line distance
+    ///
+    EUCLIDEAN,
+}
+
+/// Get the distance from every entry with data to the closest entry without data, where any entry different from
+/// zero is data. Entries without data get 0, and an entry with data touching an empty one gets 1. Everything
+/// beyond the matrix counts as empty, so the entries with data on the border get at most 1.
+///
+/// # Method
+///
+/// Every metric runs in time linear on the number of entries. Chessboard and city block take two passes over the
+/// data, forward and backward, each entry taking the smallest distance of its neighbours already seen plus one.
+/// Euclidean is exact, as described by Felzenszwalb and Huttenlocher (2012), going over every column and then
+/// every row with the lower envelope of the parabolas rooted at each entry.
+///
+pub fn distance_transform(input_data: &Vmatrix<u32>, metric: DistanceMetric) -> Vmatrix<f64> {
+    let size = input_data.size;
+
+    let distances: Vec<f64> = match metric {
+        DistanceMetric::CHESSBOARD | DistanceMetric::CITYBLOCK => {
+            chamfer(input_data, metric == DistanceMetric::CHESSBOARD).iter().map(|value| *value as f64).collect()
+        }
+        DistanceMetric::EUCLIDEAN => {
+            squared_euclidean(input_data).iter().map(|value| value.sqrt()).collect()
+        }
+    };
+
+    Vmatrix::build(size, distances)
+}
+
+/// See [distance_transform]. Chessboard and city block distances are whole numbers, get them as such.
+///
+/// # Panics
+///
+/// The metric can't be [DistanceMetric::EUCLIDEAN]
+///
+pub fn integer_distance_transform(input_data: &Vmatrix<u32>, metric: DistanceMetric) -> Vmatrix<u32> {
+    if metric == DistanceMetric::EUCLIDEAN {
+        panic!("Euclidean distances aren't whole numbers, use distance_transform instead");
+    }
+
+    Vmatrix::build(input_data.size, chamfer(input_data, metric == DistanceMetric::CHESSBOARD))
+}
+
+/// Two pass distance on the data with an empty frame around it, returned without the frame
+///
+fn chamfer(input_data: &Vmatrix<u32>, diagonals: bool) -> Vec<u32> {
+    let size = input_data.size;
+    let padded_size = size + 2;
+
+    let mut padded = vec![0u32; padded_size * padded_size];
+    for (x, y, value) in input_data.iter_coords() {
+        if value != 0 {
+            padded[(x + 1) + (y + 1) * padded_size] = u32::MAX;
+        }
+    }
+
+    // Neighbours already seen on the forward pass, the backward pass uses the opposite ones
+    let mut offsets: Vec<usize> = vec![1, padded_size];
+    if diagonals {
+        offsets.push(padded_size - 1);
+        offsets.push(padded_size + 1);
+    }
+
+    for y in 1..=size {
+        for x in 1..=size {
+            let index = x + y * padded_size;
+            if padded[index] == 0 {
+                continue;
+            }
+            let closest = offsets.iter().map(|offset| padded[index - offset]).min().unwrap_or(0);
+            padded[index] = padded[index].min(closest.saturating_add(1));
+        }
+    }
+
+    for y in (1..=size).rev() {
+        for x in (1..=size).rev() {
+            let index = x + y * padded_size;
+            if padded[index] == 0 {
+                continue;
+            }
+            let closest = offsets.iter().map(|offset| padded[index + offset]).min().unwrap_or(0);
+            padded[index] = padded[index].min(closest.saturating_add(1));
+        }
+    }
+
+    let mut result: Vec<u32> = Vec::with_capacity(size * size);
+    for y in 1..=size {
+        result.extend_from_slice(&padded[(1 + y * padded_size)..=(size + y * padded_size)]);
+    }
+
+    result
+}
+
+/// Squared euclidean distance on the data with an empty frame around it, returned without the frame
+///
+fn squared_euclidean(input_data: &Vmatrix<u32>) -> Vec<f64> {
+    let size = input_data.size;
+    let padded_size = size + 2;
+
+    let mut padded = vec![0.0f64; padded_size * padded_size];
+    for (x, y, value) in input_data.iter_coords() {
+        if value != 0 {
+            padded[(x + 1) + (y + 1) * padded_size] = f64::INFINITY;
+        }
+    }
+
+    let mut line = vec![0.0f64; padded_size];
+    for x in 0..padded_size {
+        for y in 0..padded_size {
+            line[y] = padded[x + y * padded_size];
+        }
+        let transformed = lower_envelope(&line);
+        for y in 0..padded_size {
+            padded[x + y * padded_size] = transformed[y];
+        }
+    }
+    for y in 0..padded_size {
+        let row = y * padded_size;
+        let transformed = lower_envelope(&padded[row..(row + padded_size)]);
+        padded[row..(row + padded_size)].copy_from_slice(&transformed);
+    }
+
+    let mut result: Vec<f64> = Vec::with_capacity(size * size);
+    for y in 1..=size {
+        result.extend_from_slice(&padded[(1 + y * padded_size)..=(size + y * padded_size)]);
+    }
+
+    result
+}
+
+/// One dimensional squared distance, the smallest (q - p)^2 + f(p) for every position q
+///
+fn lower_envelope(values: &[f64]) -> Vec<f64> {
+    let length = values.len();
+    let mut result = vec![f64::INFINITY; length];
+
+    // Roots of the parabolas on the envelope, and where each one starts being the lowest
+    let mut roots: Vec<usize> = Vec::with_capacity(length);
+    let mut starts: Vec<f64> = Vec::with_capacity(length + 1);
+
+    let intersection = |first: usize, second: usize| {
+        ((values[second] + (second * second) as f64) - (values[first] + (first * first) as f64)) / (2.0 * (second as f64 - first as f64))
+    };
+
+    for (position, value) in values.iter().enumerate() {
+        if value.is_infinite() {
+            continue;
+        }
+
+        while let Some(last) = roots.last() {
+            if intersection(*last, position) <= starts[starts.len() - 1] {
+                roots.pop();
+                starts.pop();
+            }
+            else {
+                break;
+            }
+        }
+
+        starts.push(match roots.last() {
+            Some(last) => intersection(*last, position),
+            None => f64::NEG_INFINITY,
+        });
+        roots.push(position);
+    }
+
+    if roots.is_empty() {
+        return result;
+    }
+
+    let mut current = 0;
+    for (position, entry) in result.iter_mut().enumerate() {
+        while current + 1 < roots.len() && starts[current + 1] < position as f64 {
+            current += 1;
+        }
+        let offset = position as f64 - roots[current] as f64;
+        *entry = offset * offset + values[roots[current]];
+    }
+
+    result
+}
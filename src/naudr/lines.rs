@@ -0,0 +1,220 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use crate::Vmatrix;
+use crate::Vector2;
+
+/// Number of angles tried by [detect_lines], evenly spread over half a turn. Multiple of 4, so horizontal,
+/// vertical and both diagonals are tried exactly.
+///
+pub const HOUGH_ANGLE_STEPS: usize = 180;
+
+/// Distance between the lines tried by [detect_lines] at the same angle
+///
+const RHO_STEP: f64 = 0.5;
+/// Entries closer than this to a line are on it. Wide enough for the stairs of any line drawn on a matrix to fit
+/// within a single line tried.
+///
+const LINE_HALF_WIDTH: f64 = 0.75;
+
+/// A straight segment found by [detect_lines]
+///
+#[derive(Clone, Debug)]
+pub struct LineSegment {
+    /// First entry of the segment, as (column, row)
+    ///
+    pub start: Vector2<usize>,
+    /// Last entry of the segment, as (column, row)
+    ///
+    pub end: Vector2<usize>,
+
+    /// Angle of the normal of the line, in radians within [0, PI). The line holds x*cos(theta) + y*sin(theta) = rho
+    ///
+    pub theta: f64,
+    /// Signed distance from the origin to the line
+    ///
+    pub rho: f64,
+
+    /// Index of every entry on the segment, from start to end
+    ///
+    pub cells: Vec<usize>,
+}
+
+impl LineSegment {
+    /// Number of entries on the segment
+    ///
+    pub fn length(&self) -> usize {
+        self.cells.len()
+    }
+}
+
+/// Find the straight segments of at least [minimum_length] entries on a matrix, at any slope, where any entry
+/// different from zero is data. This extends [crate::recurrent_trace], that only finds rows, and columns when
+/// transposing, to diagonal strokes. Segments are listed from the one with the most entries on its line.
+///
+/// # Method
+///
+/// Every entry with data votes for the lines going close to it, as (theta, rho) pairs. The most voted line is
+/// walked along the matrix and its longest run of data becomes a segment, whose entries don't vote anymore. The
+/// angle and distance of the segment are measured again from its ends, as bins only approximate them.
+/// Lines without a run long enough are discarded. This goes on until no line has enough votes left.
+///
+/// Lines are kept on a heap by their votes. Votes only ever go down, so an entry on the heap that doesn't match
+/// the votes of its line anymore is pushed back with the current count when it comes out, instead of updating
+/// the heap every time an entry stops voting.
+///
+pub fn detect_lines(input_data: &Vmatrix<u32>, minimum_length: usize) -> Vec<LineSegment> {
+    let size = input_data.size;
+    let minimum_length = minimum_length.max(2);
+
+    let angles: Vec<(f64, f64)> = (0..HOUGH_ANGLE_STEPS).map(|step| {
+        let theta = step as f64 * std::f64::consts::PI / HOUGH_ANGLE_STEPS as f64;
+        (theta.cos(), theta.sin())
+    }).collect();
+
+    let rho_offset = ((size as f64 * std::f64::consts::SQRT_2 + LINE_HALF_WIDTH) / RHO_STEP).ceil() as i64 + 1;
+    let rho_bins = 2 * rho_offset as usize + 1;
+
+    // Every bin a single entry votes for
+    let bins_of = |index: usize| {
+        let (x, y) = input_data.coordinates_of(index);
+        angles.iter().enumerate().flat_map(move |(step, (cos, sin))| {
+            let distance = x as f64 * cos + y as f64 * sin;
+            let first = ((distance - LINE_HALF_WIDTH) / RHO_STEP).ceil() as i64;
+            let last = ((distance + LINE_HALF_WIDTH) / RHO_STEP).floor() as i64;
+            (first..=last).map(move |rho| step * rho_bins + (rho + rho_offset) as usize)
+        })
+    };
+
+    let mut remaining: Vec<bool> = input_data.data.iter().map(|value| *value != 0).collect();
+    let mut votes = vec![0usize; HOUGH_ANGLE_STEPS * rho_bins];
+    for index in (0..remaining.len()).filter(|index| remaining[*index]) {
+        for bin in bins_of(index) {
+            votes[bin] += 1;
+        }
+    }
+
+    // Ties go to the lowest bin
+    let mut candidates: BinaryHeap<(usize, Reverse<usize>)> = (0..votes.len())
+        .filter(|bin| votes[*bin] >= minimum_length)
+        .map(|bin| (votes[bin], Reverse(bin)))
+        .collect();
+
+    let mut result: Vec<LineSegment> = Vec::new();
+    while let Some((count, Reverse(best))) = candidates.pop() {
+        if count != votes[best] {
+            if votes[best] >= minimum_length {
+                candidates.push((votes[best], Reverse(best)));
+            }
+            continue;
+        }
+
+        let (cos, sin) = angles[best / rho_bins];
+        let rho = ((best % rho_bins) as i64 - rho_offset) as f64 * RHO_STEP;
+
+        let run = longest_run(&line_cells(size, cos, sin, rho), &remaining);
+        if run.len() < minimum_length {
+            // Removing entries never makes a run longer, the line can be ignored from now on
+            votes[best] = 0;
+            continue;
+        }
+
+        for index in &run {
+            remaining[*index] = false;
+            for bin in bins_of(*index) {
+                votes[bin] = votes[bin].saturating_sub(1);
+            }
+        }
+
+        let (start_x, start_y) = input_data.coordinates_of(run[0]);
+        let (end_x, end_y) = input_data.coordinates_of(run[run.len() - 1]);
+
+        // The voted line is only as precise as its bin, the one through both ends is exact
+        let mut theta = (start_x as f64 - end_x as f64).atan2(end_y as f64 - start_y as f64);
+        if theta < 0.0 {
+            theta += std::f64::consts::PI;
+        }
+        if theta >= std::f64::consts::PI {
+            theta -= std::f64::consts::PI;
+        }
+        let rho = start_x as f64 * theta.cos() + start_y as f64 * theta.sin();
+
+        result.push(LineSegment {
+            start: Vector2::new(start_x, start_y),
+            end: Vector2::new(end_x, end_y),
+            theta,
+            rho,
+            cells: run,
+        });
+    }
+
+    result
+}
+
+/// Get a matrix with 1 on every entry of the segments, and 0 elsewhere. Remove the segments from the data they
+/// were found on with [Vmatrix::xat], as done with the result of [crate::recurrent_trace].
+///
+pub fn segments_mask(segments: &[LineSegment], size: usize) -> Vmatrix<u32> {
+    let mut result: Vmatrix<u32> = Vmatrix::<u32>::initialize(size, 0);
+
+    for segment in segments {
+        for index in &segment.cells {
+            result.data[*index] = 1;
+        }
+    }
+
+    result
+}
+
+/// Entries that vote for the line, grouped by their position along it, in order. Walks along the axis the line
+/// is closer to, and every group holds the entries on the line at that step.
+///
+fn line_cells(size: usize, cos: f64, sin: f64, rho: f64) -> Vec<Vec<usize>> {
+    let mut result: Vec<Vec<usize>> = Vec::new();
+    let along_columns = sin.abs() >= cos.abs();
+
+    for step in 0..size as i64 {
+        let center = if along_columns {
+            ((rho - step as f64 * cos) / sin).round() as i64
+        }
+        else {
+            ((rho - step as f64 * sin) / cos).round() as i64
+        };
+
+        let mut group: Vec<usize> = Vec::new();
+        for across in (center - 2)..=(center + 2) {
+            let (x, y) = if along_columns { (step, across) } else { (across, step) };
+            if x < 0 || y < 0 || x >= size as i64 || y >= size as i64 {
+                continue;
+            }
+            if (x as f64 * cos + y as f64 * sin - rho).abs() <= LINE_HALF_WIDTH {
+                group.push(x as usize + y as usize * size);
+            }
+        }
+        result.push(group);
+    }
+
+    result
+}
+
+/// Longest sequence of consecutive steps along the line that still have data, as the entries with data on them
+///
+fn longest_run(steps: &[Vec<usize>], remaining: &[bool]) -> Vec<usize> {
+    let mut best: Vec<usize> = Vec::new();
+    let mut current: Vec<usize> = Vec::new();
+
+    for group in steps {
+        let found: Vec<usize> = group.iter().copied().filter(|index| remaining[*index]).collect();
+        if found.is_empty() {
+            current.clear();
+            continue;
+        }
+
+        current.extend(found);
+        if current.len() > best.len() {
+            best = current.clone();
+        }
+    }
+
+    best
+}
@@ -1,10 +1,32 @@
+use std::fmt;
+use std::error::Error;
+
 use crate::Vmatrix;
+use crate::BitMatrix;
+
+/// Error returned when combining two Vmatrix that don't share their size
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct SizeMismatch {
+    pub left_size: usize,
+    pub right_size: usize,
+    pub left_length: usize,
+    pub right_length: usize,
+}
+
+impl fmt::Display for SizeMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "This operation is impossible on matrices of different size. The found sizes are {} ({} entries) and {} ({} entries)",
+               self.left_size, self.left_length, self.right_size, self.right_length)
+    }
+}
+
+impl Error for SizeMismatch {}
 
 impl<T> Vmatrix<T>
 where
     T: Clone + std::cmp::PartialEq<u32>,
 {
-    // Could be refactored to take closures and thus operate over any criterion, instead of just numbers
     /// Return a new Vmatrix that has 1 on the same entries where -self- had a number different from zero
     /// but -other- did NOT have an entry different from zero
     /// 
@@ -36,4 +58,157 @@ where
 
         result
     }
-}
\ No newline at end of file
+}
+
+impl<T> Vmatrix<T>
+where
+    T: Clone + Copy,
+{
+    /// Get a new Vmatrix of the same size with f applied to every entry
+    ///
+    pub fn map<U, F>(&self, f: F) -> Vmatrix<U>
+    where
+        U: Clone,
+        F: Fn(T) -> U,
+    {
+        Vmatrix {
+            size: self.size,
+            data: self.data.iter().map(|entry| f(*entry)).collect(),
+        }
+    }
+
+    /// Get a new Vmatrix with f applied to every pair of entries at the same index on -self- and -other-
+    ///
+    pub fn zip_map<U, V, F>(&self, other: &Vmatrix<U>, f: F) -> Result<Vmatrix<V>, SizeMismatch>
+    where
+        U: Clone + Copy,
+        V: Clone,
+        F: Fn(T, U) -> V,
+    {
+        if self.size != other.size || self.data.len() != other.data.len() {
+            return Err(SizeMismatch {
+                left_size: self.size,
+                right_size: other.size,
+                left_length: self.data.len(),
+                right_length: other.data.len(),
+            });
+        }
+
+        Ok(Vmatrix {
+            size: self.size,
+            data: self.data.iter().zip(other.data.iter()).map(|(first, second)| f(*first, *second)).collect(),
+        })
+    }
+}
+
+impl<T> Vmatrix<T>
+where
+    T: Clone + Copy + Default + PartialEq,
+{
+    /// Return a new Vmatrix with 1 where both -self- and -other- have an entry different from zero (the default
+    /// value of their type) and 0 everywhere else
+    ///
+    pub fn and<U>(&self, other: &Vmatrix<U>) -> Result<Vmatrix<u32>, SizeMismatch>
+    where
+        U: Clone + Copy + Default + PartialEq,
+    {
+        self.zip_map(other, |first, second| (is_set(first) && is_set(second)) as u32)
+    }
+
+    /// See [and]. 1 where any of them has an entry different from zero
+    ///
+    pub fn or<U>(&self, other: &Vmatrix<U>) -> Result<Vmatrix<u32>, SizeMismatch>
+    where
+        U: Clone + Copy + Default + PartialEq,
+    {
+        self.zip_map(other, |first, second| (is_set(first) || is_set(second)) as u32)
+    }
+
+    /// See [and]. 1 where only one of them has an entry different from zero
+    ///
+    pub fn xor<U>(&self, other: &Vmatrix<U>) -> Result<Vmatrix<u32>, SizeMismatch>
+    where
+        U: Clone + Copy + Default + PartialEq,
+    {
+        self.zip_map(other, |first, second| (is_set(first) != is_set(second)) as u32)
+    }
+
+    /// Return a new Vmatrix with 1 where -self- is zero and 0 everywhere else
+    ///
+    pub fn not(&self) -> Vmatrix<u32> {
+        self.map(|entry| !is_set(entry) as u32)
+    }
+}
+
+fn is_set<T>(value: T) -> bool
+where
+    T: Default + PartialEq,
+{
+    value != T::default()
+}
+
+impl BitMatrix {
+    /// See [Vmatrix::xat]. Return a new BitMatrix set where -self- is set but -other- is not, a whole word at a time
+    ///
+    /// # Panics
+    ///
+    /// The function panics if the inputs have different size
+    ///
+    pub fn xat(&self, other: &BitMatrix) -> BitMatrix {
+        match self.zip_words(other, |first, second| first & !second) {
+            Ok(result) => result,
+            Err(mismatch) => panic!("{}", mismatch),
+        }
+    }
+
+    /// See [Vmatrix::and]. Set where both -self- and -other- are set
+    ///
+    pub fn and(&self, other: &BitMatrix) -> Result<BitMatrix, SizeMismatch> {
+        self.zip_words(other, |first, second| first & second)
+    }
+
+    /// See [Vmatrix::or]. Set where any of them is set
+    ///
+    pub fn or(&self, other: &BitMatrix) -> Result<BitMatrix, SizeMismatch> {
+        self.zip_words(other, |first, second| first | second)
+    }
+
+    /// See [Vmatrix::xor]. Set where only one of them is set
+    ///
+    pub fn xor(&self, other: &BitMatrix) -> Result<BitMatrix, SizeMismatch> {
+        self.zip_words(other, |first, second| first ^ second)
+    }
+
+    /// See [Vmatrix::not]. Set where -self- is not
+    ///
+    pub fn not(&self) -> BitMatrix {
+        let mut result = BitMatrix {
+            size: self.size,
+            words: self.words.iter().map(|word| !word).collect(),
+        };
+        result.clear_padding();
+
+        result
+    }
+
+    /// Get a new BitMatrix with f applied to every pair of words at the same position on -self- and -other-
+    ///
+    fn zip_words<F>(&self, other: &BitMatrix, f: F) -> Result<BitMatrix, SizeMismatch>
+    where
+        F: Fn(u64, u64) -> u64,
+    {
+        if self.size != other.size {
+            return Err(SizeMismatch {
+                left_size: self.size,
+                right_size: other.size,
+                left_length: self.len(),
+                right_length: other.len(),
+            });
+        }
+
+        Ok(BitMatrix {
+            size: self.size,
+            words: self.words.iter().zip(other.words.iter()).map(|(first, second)| f(*first, *second)).collect(),
+        })
+    }
+}
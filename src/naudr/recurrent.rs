@@ -1,10 +1,20 @@
 use crate::Vmatrix;
+use crate::Axis;
+use crate::BitMatrix;
 
 /// Preserve only entries that appear in a row [minimum_recursion]*times before skipping
 /// to the next row, based on input_data size as a matrix.
 ///
 pub fn recurrent_trace(input_data: &Vmatrix<u32>, minimum_recursion: usize) -> Vmatrix<u32> {
+    recurrent_trace_along(input_data, minimum_recursion, Axis::ROWS)
+}
+
+/// See [recurrent_trace]. Walk the data along the given axis, so runs on the columns are found in place, giving
+/// the same mask as transposing the data, tracing it and transposing the result back.
+///
+pub fn recurrent_trace_along(input_data: &Vmatrix<u32>, minimum_recursion: usize, axis: Axis) -> Vmatrix<u32> {
     let set_size: usize = input_data.data.len();
+    let row_size: usize = input_data.size;
 
     let working_data = &input_data.data;
     let mut result: Vmatrix<u32> = Vmatrix::<u32>::initialize(input_data.size, 0);
@@ -16,7 +26,7 @@ pub fn recurrent_trace(input_data: &Vmatrix<u32>, minimum_recursion: usize) -> V
     let mut recursion_found: bool = false;
 
     for i in 0..set_size {
-        let zero_entry = working_data[i] == 0;
+        let zero_entry = working_data[axis.index_on(i, row_size)] == 0;
 
         if anchor_count == 0 && !zero_entry {
             anchor_index = i;
@@ -33,15 +43,164 @@ pub fn recurrent_trace(input_data: &Vmatrix<u32>, minimum_recursion: usize) -> V
                 recursion_found = true;
                 let mut j: usize = anchor_index;
                 while j != i {
-                    working_result[j] = 1;
+                    working_result[axis.index_on(j, row_size)] = 1;
                     j += 1;
                 }
             }
         }
         if recursion_found {
-            working_result[i] = 1;
+            working_result[axis.index_on(i, row_size)] = 1;
         }
     }
 
     result
-}
\ No newline at end of file
+}
+
+/// See [recurrent_trace]. Trace packed data, finding the entries that start a long enough run by matching the
+/// data against copies of itself moved by one entry after another, and then spreading those starts over the
+/// run, a whole word at a time. As on [recurrent_trace] the data is read as a single line, so runs carry on
+/// from the end of a row to the next one, and a single entry is never a run.
+///
+pub fn recurrent_trace_bits(input_data: &BitMatrix, minimum_recursion: usize) -> BitMatrix {
+    recurrent_trace_bits_along(input_data, minimum_recursion, Axis::ROWS)
+}
+
+/// See [recurrent_trace_bits] and [recurrent_trace_along]. Along columns the copies are moved by a whole row for
+/// every entry, and runs carry on from the bottom of a column to the top of the next one.
+///
+pub fn recurrent_trace_bits_along(input_data: &BitMatrix, minimum_recursion: usize, axis: Axis) -> BitMatrix {
+    let run_length = minimum_recursion.max(2);
+
+    let mut starts = input_data.clone();
+    let mut ahead = input_data.clone();
+    for _ in 1..run_length {
+        ahead = step_along(&ahead, axis, true);
+        starts = starts.and(&ahead).expect("Shifted data shares the size of the input");
+    }
+
+    let mut result = starts.clone();
+    let mut behind = starts;
+    for _ in 1..run_length {
+        behind = step_along(&behind, axis, false);
+        result = result.or(&behind).expect("Shifted data shares the size of the input");
+    }
+
+    result
+}
+
+/// Get a new BitMatrix where every entry takes the value of the one right after it along the axis, or right
+/// before it when not going forward, as read by [recurrent_trace_along]
+///
+fn step_along(input_data: &BitMatrix, axis: Axis, forward: bool) -> BitMatrix {
+    let size = input_data.size;
+    let length = input_data.len();
+
+    match (axis, forward) {
+        (Axis::ROWS, true) => input_data.shifted(1),
+        (Axis::ROWS, false) => input_data.shifted(-1),
+        (Axis::COLUMNS, _) if size < 2 => BitMatrix::new(size),
+        (Axis::COLUMNS, true) => {
+            // Within a column the next entry is a row below, the last row goes on with the top of the next column
+            let mut within = BitMatrix::new(size);
+            within.fill(0, length - size, true);
+            let mut wrapping = BitMatrix::new(size);
+            wrapping.fill(length - size, length - 1, true);
+
+            let below = input_data.shifted(size as isize).and(&within).expect("Masks share the size of the input");
+            let next_top = input_data.shifted(1 - (length - size) as isize).and(&wrapping).expect("Masks share the size of the input");
+            below.or(&next_top).expect("Masks share the size of the input")
+        },
+        (Axis::COLUMNS, false) => {
+            let mut within = BitMatrix::new(size);
+            within.fill(size, length, true);
+            let mut wrapping = BitMatrix::new(size);
+            wrapping.fill(1, size, true);
+
+            let above = input_data.shifted(-(size as isize)).and(&within).expect("Masks share the size of the input");
+            let previous_bottom = input_data.shifted((length - size) as isize - 1).and(&wrapping).expect("Masks share the size of the input");
+            above.or(&previous_bottom).expect("Masks share the size of the input")
+        },
+    }
+}
+
+/// A run of consecutive entries on a single row or column, as found by [recurrent_runs]
+///
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct RunSegment {
+    /// Whether the run goes along a row or a column
+    ///
+    pub axis: Axis,
+    /// Row of the run when going along a row, column otherwise
+    ///
+    pub line: usize,
+    /// Position of the first entry within its row or column
+    ///
+    pub start: usize,
+    /// Position of the last entry within its row or column, included in the run
+    ///
+    pub end: usize,
+    /// Number of entries in the run
+    ///
+    pub length: usize,
+}
+
+impl RunSegment {
+    /// Index of every entry of the run, on a matrix of the given size
+    ///
+    pub fn indexes(&self, size: usize) -> Vec<usize> {
+        (self.start..=self.end).map(|position| match self.axis {
+            Axis::ROWS => position + self.line * size,
+            Axis::COLUMNS => self.line + position * size,
+        }).collect()
+    }
+}
+
+/// Result of [recurrent_runs]
+///
+pub struct RecurrentRuns {
+    /// Same mask [recurrent_trace] returns, always on the frame of the input
+    ///
+    pub mask: Vmatrix<u32>,
+    /// Every run of the mask, ordered by line and then by start
+    ///
+    pub segments: Vec<RunSegment>,
+}
+
+/// See [recurrent_trace_along]. Keep the runs found as segments besides the mask, so straight strokes can be
+/// described without looking for curves on them.
+///
+/// The segments describe the mask exactly, so a run [recurrent_trace] continued from the end of a row onto the
+/// next one is split in two segments.
+///
+pub fn recurrent_runs(input_data: &Vmatrix<u32>, minimum_recursion: usize, axis: Axis) -> RecurrentRuns {
+    let mask = recurrent_trace_along(input_data, minimum_recursion, axis);
+    let size = mask.size;
+
+    let mut segments: Vec<RunSegment> = Vec::new();
+    for line in 0..size {
+        let mut start: Option<usize> = None;
+
+        for position in 0..=size {
+            let found = position < size && mask.data[axis.index_on(position + line * size, size)] != 0;
+            match (start, found) {
+                (None, true) => start = Some(position),
+                (Some(first), false) => {
+                    segments.push(RunSegment {
+                        axis,
+                        line,
+                        start: first,
+                        end: position - 1,
+                        length: position - first,
+                    });
+                    start = None;
+                }
+                _ => {}
+            }
+        }
+    }
+
+    RecurrentRuns {
+        mask,
+        segments,
+    }
+}
@@ -0,0 +1,146 @@
+use std::fs;
+
+use crate::Vmatrix;
+use crate::GlobalCurveData;
+use crate::naudr::vectorise::curve_points;
+
+/// A visual report of the data on a matrix, written as SVG. Every call adds a layer on top of the previous ones,
+/// so the order of the calls is the order they are drawn in.
+///
+/// # Example
+///
+/// Add the input data first, then the dominant lines, the curves and the bloats, and write it out with
+/// [SvgReport::write_to_file]. See [crate::get_svg_report] for the whole process on a single glyph.
+///
+pub struct SvgReport {
+    /// Number of entries per row of the matrices drawn
+    ///
+    pub size: usize,
+    /// Side of every entry on the drawing, in pixels
+    ///
+    pub scale: f64,
+
+    /// Every layer already written as a SVG group
+    ///
+    pub layers: Vec<String>,
+}
+
+impl SvgReport {
+    pub fn new(size: usize, scale: f64) -> SvgReport {
+        SvgReport {
+            size,
+            scale,
+            layers: Vec::new(),
+        }
+    }
+
+    /// Draw a square of the given colour on every entry of the layer different from zero
+    ///
+    pub fn add_matrix_layer(&mut self, name: &str, layer: &Vmatrix<u32>, colour: &str, opacity: f64) {
+        let mut group = self.open_group(name);
+        for (x, y, value) in layer.iter_coords() {
+            if value == 0 {
+                continue;
+            }
+
+            group += &format!("<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\" fill-opacity=\"{}\"/>\n",
+                              x as f64 * self.scale, y as f64 * self.scale, self.scale, self.scale, colour, opacity);
+        }
+
+        self.close_group(group);
+    }
+
+    /// See [SvgReport::add_matrix_layer]. Draw the dominant lines in a colour of their own, so they are told
+    /// apart from the curves.
+    ///
+    pub fn add_dominant_overlay(&mut self, dominant: &Vmatrix<u32>) {
+        self.add_matrix_layer("dominant", dominant, "#d33", 0.35);
+    }
+
+    /// Draw every curve on the global data with its own colour, and join its points with a line following the
+    /// order they were marked in. The first point of every curve is drawn bigger and labelled with its number.
+    /// Curves land on the same frame as the other layers whatever the axis they are stored along.
+    ///
+    pub fn add_curves(&mut self, global_data: &GlobalCurveData) {
+        let mut group = self.open_group("curves");
+        for (curve, points) in curve_points(global_data) {
+            let colour = curve_colour(curve);
+
+            let mut polyline = String::from("");
+            for point in &points {
+                let (center_x, center_y) = self.center_of(point.x, point.y);
+                polyline += &format!("{},{} ", center_x, center_y);
+                group += &format!("<circle cx=\"{}\" cy=\"{}\" r=\"{}\" fill=\"{}\"/>\n", center_x, center_y, self.scale * 0.2, colour);
+            }
+            group += &format!("<polyline points=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"{}\"/>\n",
+                              polyline.trim_end(), colour, self.scale * 0.1);
+
+            let (start_x, start_y) = self.center_of(points[0].x, points[0].y);
+            group += &format!("<circle cx=\"{}\" cy=\"{}\" r=\"{}\" fill=\"none\" stroke=\"{}\"/>\n", start_x, start_y, self.scale * 0.4, colour);
+            group += &format!("<text x=\"{}\" y=\"{}\" font-size=\"{}\" fill=\"{}\">{}</text>\n",
+                              start_x + self.scale * 0.5, start_y, self.scale, colour, curve);
+        }
+
+        self.close_group(group);
+    }
+
+    /// Draw the bloats written on the global data by [crate::write_bloats] as circles. An entry with a bloat of n
+    /// contains the square of side 2n - 1 around it, so its circle has a radius of n - 0.5 entries.
+    ///
+    pub fn add_bloat_circles(&mut self, bloat_data: &GlobalCurveData) {
+        let mut group = self.open_group("bloats");
+        for (x, y, radius) in bloat_data.curves_global_output.iter_coords() {
+            if radius == 0 {
+                continue;
+            }
+
+            let (center_x, center_y) = self.center_of(x as f64, y as f64);
+            group += &format!("<circle cx=\"{}\" cy=\"{}\" r=\"{}\" fill=\"none\" stroke=\"#1a7\" stroke-width=\"{}\"/>\n",
+                              center_x, center_y, (radius as f64 - 0.5) * self.scale, self.scale * 0.1);
+        }
+
+        self.close_group(group);
+    }
+
+    /// Get the whole report as a SVG document
+    ///
+    pub fn to_svg(&self) -> String {
+        let side = self.size as f64 * self.scale;
+
+        let mut result = format!("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" viewBox=\"0 0 {} {}\">\n",
+                                 side, side, side, side);
+        result += &format!("<rect width=\"{}\" height=\"{}\" fill=\"white\"/>\n", side, side);
+        for layer in &self.layers {
+            result += layer;
+        }
+        result += "</svg>\n";
+
+        result
+    }
+
+    /// See [SvgReport::to_svg]. Write the report to a file, usually with the ".svg" extension.
+    ///
+    pub fn write_to_file(&self, file_path: String) -> std::io::Result<()> {
+        fs::write(file_path, self.to_svg())
+    }
+
+    fn open_group(&self, name: &str) -> String {
+        format!("<g id=\"{}\">\n", name)
+    }
+
+    fn close_group(&mut self, group: String) {
+        self.layers.push(group + "</g>\n");
+    }
+
+    fn center_of(&self, x: f64, y: f64) -> (f64, f64) {
+        ((x + 0.5) * self.scale, (y + 0.5) * self.scale)
+    }
+}
+
+/// Get a colour for a curve number. Consecutive numbers are spread around the colour wheel so neighbouring
+/// curves are easy to tell apart.
+///
+pub fn curve_colour(curve: u32) -> String {
+    let hue = (curve as u64 * 137) % 360;
+    format!("hsl({}, 70%, 45%)", hue)
+}
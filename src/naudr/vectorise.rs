@@ -0,0 +1,262 @@
+use crate::Vmatrix;
+use crate::Vector2;
+use crate::GlobalCurveData;
+
+/// Kind of segments [fit_path] joins the simplified points with
+///
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum CurveFit {
+    LINES,
+    QUADRATIC,
+    CUBIC,
+}
+
+/// A single piece of a [VectorPath]. Every segment starts where the previous one ended.
+///
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum PathSegment {
+    /// Straight line to the end point
+    ///
+    LINE(Vector2<f64>),
+    /// Quadratic Bézier with a control point and the end point
+    ///
+    QUADRATIC(Vector2<f64>, Vector2<f64>),
+    /// Cubic Bézier with two control points and the end point
+    ///
+    CUBIC(Vector2<f64>, Vector2<f64>, Vector2<f64>),
+}
+
+/// A path made of segments, as it would be drawn from its start
+///
+#[derive(PartialEq, Debug, Clone)]
+pub struct VectorPath {
+    pub start: Vector2<f64>,
+    pub segments: Vec<PathSegment>,
+}
+
+impl VectorPath {
+    /// Write the path as the data of a SVG path element, this is the value of its "d" attribute
+    ///
+    pub fn to_svg_path(&self) -> String {
+        let mut result = format!("M {} {}", self.start.x, self.start.y);
+
+        for segment in &self.segments {
+            match segment {
+                PathSegment::LINE(end) => {
+                    result += &format!(" L {} {}", end.x, end.y);
+                }
+                PathSegment::QUADRATIC(control, end) => {
+                    result += &format!(" Q {} {} {} {}", control.x, control.y, end.x, end.y);
+                }
+                PathSegment::CUBIC(control_1, control_2, end) => {
+                    result += &format!(" C {} {} {} {} {} {}", control_1.x, control_1.y, control_2.x, control_2.y, end.x, end.y);
+                }
+            }
+        }
+
+        result
+    }
+}
+
+/// Get the points of every curve written on the global data, in the order they were marked, as (curve, points).
+/// Points are (column, row) coordinates on the data the curves were found on, whatever the axis they are stored
+/// along, see [GlobalCurveData::axis]. Curves are listed by their number.
+///
+pub fn curve_points(global_data: &GlobalCurveData) -> Vec<(u32, Vec<Vector2<f64>>)> {
+    let output = &global_data.curves_global_output;
+    let orderd = &global_data.curves_global_orderd;
+
+    let mut entries: Vec<(u32, u32, usize)> = Vec::new();
+    for (index, curve) in output.data.iter().enumerate() {
+        if *curve != 0 {
+            entries.push((*curve, orderd.data[index], index));
+        }
+    }
+    entries.sort();
+
+    let mut result: Vec<(u32, Vec<Vector2<f64>>)> = Vec::new();
+    for (curve, _, index) in entries {
+        let point = index_as_point(output, global_data.axis.index_on(index, global_data.row_size));
+        match result.last_mut() {
+            Some((last_curve, points)) if *last_curve == curve => points.push(point),
+            _ => result.push((curve, vec![point])),
+        }
+    }
+
+    result
+}
+
+/// See [curve_points]. Get the points of an outline, like those returned by [trace_contours].
+///
+pub fn contour_points(outline: &[Vector2<i32>]) -> Vec<Vector2<f64>> {
+    outline.iter().map(|point| Vector2::new(point.x as f64, point.y as f64)).collect()
+}
+
+/// Reduce a polyline to the points needed to keep every removed point closer than epsilon to the result,
+/// following Ramer-Douglas-Peucker. The first and last points are always kept.
+///
+pub fn simplify_rdp(points: &[Vector2<f64>], epsilon: f64) -> Vec<Vector2<f64>> {
+    rdp_indexes(points, epsilon).iter().map(|index| points[*index]).collect()
+}
+
+/// Simplify the points with [simplify_rdp] and join the points kept with the requested segments. Béziers are fit
+/// by least squares to the original points between every pair of kept points, so the curve goes through the kept
+/// points and bends towards the removed ones.
+///
+/// # Panics
+///
+/// There has to be at least one point
+///
+pub fn fit_path(points: &[Vector2<f64>], epsilon: f64, fit: CurveFit) -> VectorPath {
+    if points.is_empty() {
+        panic!("A path can't be fit to an empty list of points");
+    }
+
+    let mut path = VectorPath {
+        start: points[0],
+        segments: Vec::new(),
+    };
+
+    for pair in rdp_indexes(points, epsilon).windows(2) {
+        let span = &points[pair[0]..=pair[1]];
+
+        let segment = match fit {
+            CurveFit::LINES => PathSegment::LINE(points[pair[1]]),
+            CurveFit::QUADRATIC => fit_quadratic(span),
+            CurveFit::CUBIC => fit_cubic(span),
+        };
+        path.segments.push(segment);
+    }
+
+    path
+}
+
+/// See [curve_points] and [fit_path]. Get the path of every curve on the global data.
+///
+pub fn vectorise_curves(global_data: &GlobalCurveData, epsilon: f64, fit: CurveFit) -> Vec<(u32, VectorPath)> {
+    curve_points(global_data).into_iter()
+        .map(|(curve, points)| (curve, fit_path(&points, epsilon, fit)))
+        .collect()
+}
+
+/// See [simplify_rdp]. Get the positions of the points kept, in order.
+///
+fn rdp_indexes(points: &[Vector2<f64>], epsilon: f64) -> Vec<usize> {
+    if points.len() < 3 {
+        return (0..points.len()).collect();
+    }
+
+    let mut keep = vec![false; points.len()];
+    keep[0] = true;
+    keep[points.len() - 1] = true;
+
+    let mut pending: Vec<(usize, usize)> = vec![(0, points.len() - 1)];
+    while let Some((first, last)) = pending.pop() {
+        let mut farthest = first;
+        let mut farthest_distance = 0.0;
+        for index in (first + 1)..last {
+            let distance = distance_to_segment(&points[index], &points[first], &points[last]);
+            if distance > farthest_distance {
+                farthest = index;
+                farthest_distance = distance;
+            }
+        }
+
+        if farthest_distance > epsilon {
+            keep[farthest] = true;
+            pending.push((first, farthest));
+            pending.push((farthest, last));
+        }
+    }
+
+    (0..points.len()).filter(|index| keep[*index]).collect()
+}
+
+fn index_as_point(matrix: &Vmatrix<u32>, index: usize) -> Vector2<f64> {
+    let (x, y) = matrix.coordinates_of(index);
+    Vector2::new(x as f64, y as f64)
+}
+
+fn distance_to_segment(point: &Vector2<f64>, start: &Vector2<f64>, end: &Vector2<f64>) -> f64 {
+    let segment = *end - *start;
+    let length = segment.magnitude();
+    if length == 0.0 {
+        return (*point - *start).magnitude();
+    }
+
+    segment.cross(&(*point - *start)).abs() / length
+}
+
+/// Parameter of every point along the span, proportional to the length walked up to it
+///
+fn chord_parameters(span: &[Vector2<f64>]) -> Vec<f64> {
+    let mut parameters = vec![0.0];
+    for pair in span.windows(2) {
+        let walked = parameters[parameters.len() - 1] + (pair[1] - pair[0]).magnitude();
+        parameters.push(walked);
+    }
+
+    let total = parameters[parameters.len() - 1];
+    if total == 0.0 {
+        return parameters;
+    }
+    parameters.iter().map(|walked| walked / total).collect()
+}
+
+fn fit_quadratic(span: &[Vector2<f64>]) -> PathSegment {
+    let start = span[0];
+    let end = span[span.len() - 1];
+    let parameters = chord_parameters(span);
+
+    let mut weighted_sum = Vector2::new(0.0, 0.0);
+    let mut weight_total = 0.0;
+    for (point, t) in span.iter().zip(parameters.iter()) {
+        let weight = 2.0 * t * (1.0 - t);
+        let remainder = *point - start * ((1.0 - t) * (1.0 - t)) - end * (t * t);
+        weighted_sum = weighted_sum + remainder * weight;
+        weight_total += weight * weight;
+    }
+
+    if weight_total == 0.0 {
+        return PathSegment::QUADRATIC((start + end) * 0.5, end);
+    }
+
+    PathSegment::QUADRATIC(weighted_sum * (1.0 / weight_total), end)
+}
+
+fn fit_cubic(span: &[Vector2<f64>]) -> PathSegment {
+    let start = span[0];
+    let end = span[span.len() - 1];
+    let parameters = chord_parameters(span);
+
+    // Normal equations for the two control points, with the end points fixed
+    let mut c11 = 0.0;
+    let mut c12 = 0.0;
+    let mut c22 = 0.0;
+    let mut x1 = Vector2::new(0.0, 0.0);
+    let mut x2 = Vector2::new(0.0, 0.0);
+    for (point, t) in span.iter().zip(parameters.iter()) {
+        let inverse = 1.0 - t;
+        let b0 = inverse * inverse * inverse;
+        let b1 = 3.0 * t * inverse * inverse;
+        let b2 = 3.0 * t * t * inverse;
+        let b3 = t * t * t;
+
+        let remainder = *point - start * b0 - end * b3;
+        c11 += b1 * b1;
+        c12 += b1 * b2;
+        c22 += b2 * b2;
+        x1 = x1 + remainder * b1;
+        x2 = x2 + remainder * b2;
+    }
+
+    let determinant = c11 * c22 - c12 * c12;
+    if determinant.abs() < f64::EPSILON {
+        let chord = end - start;
+        return PathSegment::CUBIC(start + chord * (1.0 / 3.0), start + chord * (2.0 / 3.0), end);
+    }
+
+    let control_1 = (x1 * c22 - x2 * c12) * (1.0 / determinant);
+    let control_2 = (x2 * c11 - x1 * c12) * (1.0 / determinant);
+    PathSegment::CUBIC(control_1, control_2, end)
+}
@@ -3,8 +3,12 @@ use std::error::Error;
 use std::vec::Vec;
 
 use std::fs;
+use std::path::Path;
 
 use mvecops::def::vmatrix::*;
+use mvecops::def::bitmatrix::*;
+use mvecops::def::sink::*;
+use mvecops::def::transform::*;
 use mvecops::def::trigonometric::*;
 use mvecops::def::maths::*;
 use mvecops::naudr::accumulate::*;
@@ -12,6 +16,12 @@ use mvecops::naudr::recurrent::*;
 use mvecops::naudr::operate::*;
 use mvecops::naudr::closed_curves::*;
 use mvecops::naudr::bloat::*;
+use mvecops::naudr::components::*;
+use mvecops::naudr::contours::*;
+use mvecops::naudr::vectorise::*;
+use mvecops::naudr::svg::*;
+use mvecops::naudr::lines::*;
+use mvecops::naudr::distance::*;
 
 use mvecops::*;
 
@@ -30,6 +40,42 @@ const SAMPLE_OUTPUT_BLOATC: &str = "bloating_combined.txt";
 const SAMPLE_OUTPUT_DMCO1: &str = "combined1.txt";
 const SAMPLE_OUTPUT_DMCO2: &str = "combined2.txt";
 
+/// Outputs of the tests go to the scratch directory cargo keeps for them, not to the working directory
+///
+fn output_path(name: &str) -> String {
+    Path::new(env!("CARGO_TARGET_TMPDIR")).join(name).to_string_lossy().into_owned()
+}
+
+/// Bloats found walking every square around every entry with [trace_at] and [clean_at], as write_bloats used to
+///
+fn square_walk_bloats(input_data: &Vmatrix<u32>, sample_size: usize) -> Vec<u32> {
+    let mut expected = vec![0u32; sample_size * sample_size];
+    let mut bloat_level: u32 = 1;
+    let mut bloat_increased = true;
+    while bloat_level < (sample_size / 2) as u32 && bloat_increased {
+        bloat_increased = false;
+        for i in 0..expected.len() {
+            if !input_data.test_border_index(i) && input_data.data[i] == 1 && trace_at(input_data, bloat_level, sample_size, i) {
+                expected[i] = bloat_level;
+                bloat_increased = true;
+            }
+        }
+        bloat_level += 1;
+    }
+    while bloat_level > 0 {
+        for i in 0..expected.len() {
+            if !input_data.test_border_index(i) && input_data.data[i] == 1 && expected[i] == bloat_level {
+                for j in 1..(bloat_level + 1) {
+                    clean_at(input_data, bloat_level, j, sample_size, i, &mut expected);
+                }
+            }
+        }
+        bloat_level -= 1;
+    }
+
+    expected
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -97,13 +143,13 @@ mod tests {
             }    
             complete_message += &String::from("\n");
         }
-        fs::write(SAMPLE_OUTPUT_CLEAR, complete_message);
+        fs::write(output_path(SAMPLE_OUTPUT_CLEAR), complete_message);
     }
 
     #[test]
     fn write_out_sample_reductions() {
         let accumulations: Vmatrix<u32> = get_accumulations_from_file(SAMPLE_INPUT_PATH.to_string(), 64);
-        accumulations.write_to_file(SAMPLE_OUTPUT_ACC.to_string());
+        accumulations.write_to_file(output_path(SAMPLE_OUTPUT_ACC));
     }
 
     #[test]
@@ -179,12 +225,12 @@ mod tests {
         let mut input_data: Vmatrix<u32> = textfile_to_vmatrix(SAMPLE_INPUT_PATH.to_string(), sample_size);
 
         let dominant_vertical: Vmatrix<u32> = recurrent_trace(&input_data, 12);
-        dominant_vertical.write_to_file(SAMPLE_OUTPUT_DOMVE.to_string());
+        dominant_vertical.write_to_file(output_path(SAMPLE_OUTPUT_DOMVE));
 
         input_data.transpose();
 
         let dominant_horizontal: Vmatrix<u32> = recurrent_trace(&input_data, 12);
-        dominant_horizontal.write_to_file(SAMPLE_OUTPUT_DOMHR.to_string());         
+        dominant_horizontal.write_to_file(output_path(SAMPLE_OUTPUT_DOMHR));         
     }
 
     #[test]
@@ -226,6 +272,31 @@ mod tests {
         assert_eq!(result.data, expected_result_dt5);
     }
 
+    #[test]
+    fn vmatrix_boolean_operations() {
+        let data_sample_1: Vmatrix<u32> = Vmatrix::build(2, vec![0, 3, 0, 1]);
+        let data_sample_2: Vmatrix<f64> = Vmatrix::build(2, vec![0.0, 0.0, 2.5, 1.0]);
+
+        assert_eq!(data_sample_1.and(&data_sample_2).unwrap().data, vec![0, 0, 0, 1]);
+        assert_eq!(data_sample_1.or(&data_sample_2).unwrap().data, vec![0, 1, 1, 1]);
+        assert_eq!(data_sample_1.xor(&data_sample_2).unwrap().data, vec![0, 1, 1, 0]);
+        assert_eq!(data_sample_1.not().data, vec![1, 0, 1, 0]);
+        assert_eq!(data_sample_1.map(|entry| entry * 2).data, vec![0, 6, 0, 2]);
+
+        let combined = data_sample_1.zip_map(&data_sample_2, |first, second| first as f64 + second).unwrap();
+        assert_eq!(combined.data, vec![0.0, 3.0, 2.5, 2.0]);
+
+        let data_sample_3: Vmatrix<u32> = Vmatrix::build(2, vec![2, 0, 5, 4]);
+        let xat_result = data_sample_1.xat(&data_sample_3);
+        assert_eq!(xat_result.data, vec![0, 1, 0, 0]);
+        assert_eq!(xat_result.data, data_sample_1.and(&data_sample_3.not()).unwrap().data);
+
+        let wrong_size: Vmatrix<u32> = Vmatrix::initialize(3, 0);
+        let error = data_sample_1.and(&wrong_size).unwrap_err();
+        assert_eq!((error.left_size, error.right_size), (2, 3));
+        assert_eq!(data_sample_1.zip_map(&wrong_size, |first, _| first).is_err(), true);
+    }
+
     #[test]
     fn generate_substraction_curves() {
         let sample_size: usize = 64;
@@ -234,7 +305,7 @@ mod tests {
         let accumulations: Vmatrix<u32> = get_accumulations_from_file(SAMPLE_INPUT_PATH.to_string(), sample_size);
         let mut substraction_result = get_substractions_from_data(accumulations, sample_size, dominants_recurrency);
 
-        substraction_result.write_to_file(SAMPLE_OUTPUT_SUBST.to_string());
+        substraction_result.write_to_file(output_path(SAMPLE_OUTPUT_SUBST));
     }
 
     #[test]
@@ -314,6 +385,36 @@ mod tests {
         assert_eq!(result_index, 1567);
     }
 
+    #[test]
+    fn cardinal_directions() {
+        let starting_index: usize = 1502;
+        let row_size: usize = 64;
+
+        assert_eq!(Cardinal::from_trigonometric(&Trigonometric::COS), Cardinal::E);
+        assert_eq!(Cardinal::E.rotate(1), Cardinal::SE);
+        assert_eq!(Cardinal::E.rotate(-1), Cardinal::NE);
+        assert_eq!(Cardinal::NW.rotate(10), Cardinal::NE);
+        assert_eq!(Cardinal::SW.opposite(), Cardinal::NE);
+        assert_eq!(Cardinal::N.opposite(), Cardinal::S);
+        assert_eq!(Cardinal::NE.is_diagonal(), true);
+        assert_eq!(Cardinal::W.is_diagonal(), false);
+        assert_eq!(Cardinal::SW.as_delta(), Vector2::new(-1, 1));
+
+        for value in 0..4 {
+            let axis = Trigonometric::from_int(value);
+            let cardinal = Cardinal::from_trigonometric(&axis);
+            assert_eq!(cardinal.rotate(2), Cardinal::from_trigonometric(&Trigonometric::derivative(&axis)));
+            assert_eq!(Cardinal::get_index_from_direction(starting_index, row_size, &cardinal),
+                       Trigonometric::get_index_from_direction(starting_index, row_size, &axis, 0));
+            assert_eq!(Cardinal::get_index_from_direction(starting_index, row_size, &cardinal.rotate(1)),
+                       Trigonometric::get_index_from_direction(starting_index, row_size, &axis, -1));
+            assert_eq!(Cardinal::get_index_from_direction(starting_index, row_size, &cardinal.rotate(-1)),
+                       Trigonometric::get_index_from_direction(starting_index, row_size, &axis, 1));
+        }
+
+        assert_eq!(Cardinal::get_index_from_direction(0, row_size, &Cardinal::NW), usize::MAX);
+    }
+
     #[test]
     fn valid_index_on_vmatrixu32() {
         let exclusive_at_data_5 = vec![1, 0, 1, 0, 1, 1, 0, 1, 1, 0, 1, 1, 0, 0, 1, 1, 1, 0, 1, 1, 1, 0, 0, 0, 1];
@@ -327,6 +428,216 @@ mod tests {
         assert_eq!(data_sample_1.test_index(1502), false);
     }
 
+    #[test]
+    fn neighbours_stay_on_their_row() {
+        let data_sample: Vmatrix<u32> = Vmatrix::initialize(5, 0);
+
+        assert_eq!(data_sample.neighbour(12, &Cardinal::E), Some(13));
+        assert_eq!(data_sample.neighbour(12, &Cardinal::NW), Some(6));
+        assert_eq!(data_sample.neighbour(12, &Cardinal::S), Some(17));
+        assert_eq!(data_sample.neighbour(4, &Cardinal::E), None);
+        assert_eq!(data_sample.neighbour(4, &Cardinal::SE), None);
+        assert_eq!(data_sample.neighbour(5, &Cardinal::W), None);
+        assert_eq!(data_sample.neighbour(0, &Cardinal::N), None);
+        assert_eq!(data_sample.neighbour(0, &Cardinal::W), None);
+        assert_eq!(data_sample.neighbour(24, &Cardinal::S), None);
+        assert_eq!(data_sample.neighbour(25, &Cardinal::N), None);
+    }
+
+    #[test]
+    fn coordinates_on_vmatrix() {
+        let mut data_sample: Vmatrix<u32> = Vmatrix::build(3, vec![0, 1, 2, 3, 4, 5, 6, 7, 8]);
+
+        assert_eq!(data_sample.get(2, 1), Some(5));
+        assert_eq!(data_sample.get(3, 0), None);
+        assert_eq!(data_sample.get(0, 3), None);
+        assert_eq!(data_sample[(1, 2)], 7);
+        assert_eq!(data_sample.index_of(1, 2), Some(7));
+        assert_eq!(data_sample.coordinates_of(7), (1, 2));
+
+        assert_eq!(data_sample.set(0, 0, 9), true);
+        assert_eq!(data_sample.set(0, 5, 9), false);
+        data_sample[(2, 2)] = 10;
+        assert_eq!(data_sample.data, vec![9, 1, 2, 3, 4, 5, 6, 7, 10]);
+
+        let rows: Vec<Vec<u32>> = data_sample.rows().map(|row| row.to_vec()).collect();
+        assert_eq!(rows, vec![vec![9, 1, 2], vec![3, 4, 5], vec![6, 7, 10]]);
+        let cols: Vec<Vec<u32>> = data_sample.cols().collect();
+        assert_eq!(cols, vec![vec![9, 3, 6], vec![1, 4, 7], vec![2, 5, 10]]);
+
+        let coords: Vec<(usize, usize, u32)> = data_sample.iter_coords().filter(|entry| entry.2 > 6).collect();
+        assert_eq!(coords, vec![(0, 0, 9), (1, 2, 7), (2, 2, 10)]);
+
+        assert_eq!(data_sample.neighbours(4).collect::<Vec<usize>>(), vec![5, 8, 7, 6, 3, 0, 1, 2]);
+        assert_eq!(data_sample.neighbours(0).collect::<Vec<usize>>(), vec![1, 4, 3]);
+        assert_eq!(data_sample.axis_neighbours(5).collect::<Vec<usize>>(), vec![8, 4, 2]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn index_out_of_vmatrix() {
+        let data_sample: Vmatrix<u32> = Vmatrix::initialize(3, 0);
+        let value = data_sample[(3, 0)];
+    }
+
+    #[test]
+    fn label_connected_components() {
+        let sample_data_5 = vec![1, 1, 0, 0, 1,
+                                 0, 1, 0, 1, 0,
+                                 0, 0, 0, 1, 0,
+                                 1, 0, 0, 0, 0,
+                                 1, 1, 0, 1, 1];
+        let data_sample: Vmatrix<u32> = Vmatrix::build(5, sample_data_5);
+
+        let four_connected = label_components(&data_sample, Connectivity::FOUR);
+        assert_eq!(four_connected.labels.data, vec![1, 1, 0, 0, 2,
+                                                    0, 1, 0, 3, 0,
+                                                    0, 0, 0, 3, 0,
+                                                    4, 0, 0, 0, 0,
+                                                    4, 4, 0, 5, 5]);
+        assert_eq!(four_connected.components.len(), 5);
+
+        let eight_connected = label_components(&data_sample, Connectivity::EIGHT);
+        assert_eq!(eight_connected.components.len(), 4);
+        assert_eq!(eight_connected.labels.data[13], 2);
+
+        let first = &eight_connected.components[0];
+        assert_eq!((first.label, first.area), (1, 3));
+        assert_eq!(first.bounding_min, Vector2::new(0, 0));
+        assert_eq!(first.bounding_max, Vector2::new(1, 1));
+        assert_eq!(close_enough_f64(first.centroid.x, 2.0 / 3.0, 0.0001), true);
+        assert_eq!(close_enough_f64(first.centroid.y, 1.0 / 3.0, 0.0001), true);
+
+        let second = &eight_connected.components[1];
+        assert_eq!(second.area, 3);
+        assert_eq!((second.bounding_min, second.bounding_max), (Vector2::new(3, 0), Vector2::new(4, 2)));
+
+        let sample_size = 64;
+        let input_data: Vmatrix<u32> = textfile_to_vmatrix(SAMPLE_INPUT_PATH.to_string(), sample_size);
+        let kanji_components = label_components(&input_data, Connectivity::EIGHT);
+        let total_area: usize = kanji_components.components.iter().map(|component| component.area).sum();
+        assert_eq!(total_area, input_data.data.iter().filter(|entry| **entry != 0).count());
+    }
+
+    #[test]
+    fn trace_nested_contours() {
+        let sample_data_7 = vec![0, 0, 0, 0, 0, 0, 0,
+                                 0, 1, 1, 1, 1, 1, 0,
+                                 0, 1, 0, 0, 0, 1, 0,
+                                 0, 1, 0, 1, 0, 1, 0,
+                                 0, 1, 0, 0, 0, 1, 0,
+                                 0, 1, 1, 1, 1, 1, 0,
+                                 0, 0, 0, 0, 0, 0, 0];
+        let data_sample: Vmatrix<u32> = Vmatrix::build(7, sample_data_7);
+
+        let contours = trace_contours(&data_sample);
+        assert_eq!(contours.outlines.len(), 3);
+        assert_eq!(contours.is_hole, vec![false, true, false]);
+        assert_eq!(contours.parent, vec![None, Some(0), Some(1)]);
+
+        let outer = &contours.outlines[0];
+        assert_eq!(outer.len(), 16);
+        assert_eq!(outer[0], Vector2::new(1, 1));
+        assert_eq!(outer[1], Vector2::new(1, 2));
+        assert_eq!(outer[15], Vector2::new(2, 1));
+        for pair in outer.windows(2) {
+            let step = pair[1] - pair[0];
+            assert!(step.x.abs() <= 1 && step.y.abs() <= 1);
+        }
+
+        assert_eq!(contours.outlines[1].len(), 12);
+        assert_eq!(contours.outlines[1].contains(&Vector2::new(1, 1)), false);
+        assert_eq!(contours.outlines[2], vec![Vector2::new(3, 3)]);
+
+        let line_data: Vmatrix<u32> = Vmatrix::build(3, vec![1, 1, 1, 0, 0, 0, 0, 0, 1]);
+        let contours = trace_contours(&line_data);
+        assert_eq!(contours.outlines, vec![vec![Vector2::new(0, 0), Vector2::new(1, 0), Vector2::new(2, 0), Vector2::new(1, 0)],
+                                           vec![Vector2::new(2, 2)]]);
+        assert_eq!(contours.parent, vec![None, None]);
+
+        let sample_size = 64;
+        let input_data: Vmatrix<u32> = textfile_to_vmatrix(SAMPLE_INPUT_PATH.to_string(), sample_size);
+        let contours = trace_contours(&input_data);
+        let outer_count = contours.is_hole.iter().filter(|is_hole| !**is_hole).count();
+        assert_eq!(outer_count, label_components(&input_data, Connectivity::EIGHT).components.len());
+    }
+
+    #[test]
+    fn simplify_and_fit_curves() {
+        let corner: Vec<Vector2<f64>> = vec![Vector2::new(0.0, 0.0), Vector2::new(1.0, 0.0), Vector2::new(2.0, 0.1),
+                                             Vector2::new(3.0, 0.0), Vector2::new(3.0, 1.0), Vector2::new(3.0, 2.0)];
+        assert_eq!(simplify_rdp(&corner, 0.5), vec![Vector2::new(0.0, 0.0), Vector2::new(3.0, 0.0), Vector2::new(3.0, 2.0)]);
+        assert_eq!(simplify_rdp(&corner, 0.05).len(), 4);
+        assert_eq!(simplify_rdp(&corner[..2], 0.5).len(), 2);
+
+        let lines = fit_path(&corner, 0.5, CurveFit::LINES);
+        assert_eq!(lines.to_svg_path(), "M 0 0 L 3 0 L 3 2");
+
+        // Points of the quadratic Bézier (0,0) (2,4) (4,0). The fit parameters follow the length walked, not t, so
+        // the control point is only close to the original one
+        let parabola: Vec<Vector2<f64>> = (0..=8).map(|step| {
+            let t = step as f64 / 8.0;
+            Vector2::new(4.0 * t, 8.0 * t * (1.0 - t))
+        }).collect();
+        let quadratic = fit_path(&parabola, 100.0, CurveFit::QUADRATIC);
+        assert_eq!(quadratic.segments.len(), 1);
+        match quadratic.segments[0] {
+            PathSegment::QUADRATIC(control, end) => {
+                assert_eq!(end, Vector2::new(4.0, 0.0));
+                assert_eq!(close_enough_f64(control.x, 2.0, 0.3), true);
+                assert_eq!(close_enough_f64(control.y, 4.0, 0.5), true);
+            }
+            other => panic!("Expected a quadratic segment, found {:?}", other),
+        }
+
+        let straight: Vec<Vector2<f64>> = (0..=3).map(|step| Vector2::new(step as f64, step as f64)).collect();
+        let cubic = fit_path(&straight, 0.5, CurveFit::CUBIC);
+        match cubic.segments[0] {
+            PathSegment::CUBIC(control_1, control_2, end) => {
+                assert_eq!(end, Vector2::new(3.0, 3.0));
+                assert_eq!(close_enough_f64(control_1.x, control_1.y, 0.0001), true);
+                assert_eq!(close_enough_f64(control_2.x, control_2.y, 0.0001), true);
+            }
+            other => panic!("Expected a cubic segment, found {:?}", other),
+        }
+        assert_eq!(cubic.to_svg_path().starts_with("M 0 0 C "), true);
+
+        let sample_size = 64;
+        let input_data: Vmatrix<u32> = textfile_to_vmatrix(SAMPLE_INPUT_PATH.to_string(), sample_size);
+        let global_data = get_complete_inflexions_from_data(&input_data, sample_size, 12);
+        let curves = curve_points(&global_data);
+        let marked_points = global_data.curves_global_output.data.iter().filter(|entry| **entry != 0).count();
+        assert_eq!(curves.iter().map(|(_, points)| points.len()).sum::<usize>(), marked_points);
+        assert_eq!(vectorise_curves(&global_data, 1.0, CurveFit::CUBIC).len(), curves.len());
+
+        // Pipelines store the curves along the columns, the points are still on the frame of the input
+        assert_eq!(global_data.axis, Axis::COLUMNS);
+        assert_eq!(curves[0].1[..2], [Vector2::new(56.0, 5.0), Vector2::new(54.0, 12.0)]);
+        let mut rows_data = get_complete_inflexions_from_data(&input_data, sample_size, 12);
+        rows_data.to_rows();
+        assert_eq!(curve_points(&rows_data), curves);
+        let paths = vectorise_curves(&global_data, 1.0, CurveFit::LINES);
+        assert_eq!(paths, vectorise_curves(&rows_data, 1.0, CurveFit::LINES));
+        assert_eq!(paths[0].1.start, Vector2::new(56.0, 5.0));
+
+        let contours = trace_contours(&input_data);
+        let outline = contour_points(&contours.outlines[0]);
+        assert!(simplify_rdp(&outline, 1.0).len() <= outline.len());
+    }
+
+    #[test]
+    fn curves_on_the_border() {
+        let mut input_data: Vmatrix<u32> = Vmatrix::initialize(5, 0);
+        input_data.data[0] = 2;
+        input_data.data[4] = 2;
+
+        let global_data = GlobalCurveData::new(5);
+        let mut result_output: Vmatrix<u32> = Vmatrix::initialize(5, 0);
+        let returning_index = find_curve_on(&input_data, &mut result_output, &global_data, 0);
+        assert_eq!(returning_index, 0);
+        assert_eq!(result_output.data[4], 0);
+    }
+
     #[test]
     fn get_first_inflexion_curve() {
         let sample_size: usize = 64;
@@ -500,6 +811,36 @@ mod tests {
         assert_eq!((result.x, result.y), (-9, -9));
     }
 
+    #[test]
+    fn vector_operators() {
+        let input1: Vector2<i64> = Vector2::new(3, -4);
+        let input2: Vector2<i64> = Vector2::new(-1, 2);
+        assert_eq!(input1 + input2, Vector2::new(2, -2));
+        assert_eq!(input1 - input2, sub_vectors(&input1, &input2));
+        assert_eq!(input1 * 2, Vector2::new(6, -8));
+        assert_eq!(-input1, Vector2::new(-3, 4));
+        assert_eq!(input1.dot(&input2), -11);
+        assert_eq!(input1.cross(&input2), 2);
+        assert_eq!(input1.magnitude(), 5.0);
+        assert_eq!(input1.normalise(), Vector2::new(0.6, -0.8));
+        assert_eq!(Vector2::new(0.0, 0.0).normalise(), Vector2::new(0.0, 0.0));
+
+        let input1: Vector3<i32> = Vector3::new(1, 0, 0);
+        let input2: Vector3<i32> = Vector3::new(0, 1, 0);
+        assert_eq!(input1.cross(&input2), Vector3::new(0, 0, 1));
+        assert_eq!(input1 + input2 - input1 * 2, Vector3::new(-1, 1, 0));
+        assert_eq!(-input2, Vector3::new(0, -1, 0));
+        assert_eq!(input1.dot(&input2), 0);
+        assert_eq!(Vector3::new(2.0, 3.0, 6.0).magnitude(), 7.0);
+        assert_eq!(Vector3::new(0.0, 0.0, 4.0).normalise(), Vector3::new(0.0, 0.0, 1.0));
+
+        let input1: Vector2<usize> = Vector2::new(6, 8);
+        assert_eq!(input1 / 2, Vector2::new(3, 4));
+        assert_eq!(input1.dot(&Vector2::new(1, 1)), 14);
+        assert_eq!(input1.magnitude(), 10.0);
+        assert_eq!(scale_vector(&Vector2::new(-7, 6), 3), Vector2::new(-3, 2));
+    }
+
     #[test]
     fn displace_index_with_vectors() {
         let row_size = 64; 
@@ -619,7 +960,7 @@ mod tests {
         Err(error) => panic!("Input data couldn't be retrieved: {}", error),
         Ok(all_data) => {
             let result = get_bloat_data(all_data, sample_size);
-            result.curves_global_output.write_to_file(SAMPLE_OUTPUT_BLOAT.to_string());
+            result.curves_global_output.write_to_file(output_path(SAMPLE_OUTPUT_BLOAT));
         }}
     }
 
@@ -632,10 +973,572 @@ mod tests {
         Err(error) => panic!("Input data couldn't be retrieved: {}", error),
         Ok(all_data) => {
             let result = get_combined_data(all_data, sample_size, dominants_recurrency);
-            result.1.curves_global_output.write_to_file(SAMPLE_OUTPUT_BLOATC.to_string());
-            result.0.curves_global_output.write_to_file(SAMPLE_OUTPUT_DMCO1.to_string());
-            result.0.curves_global_orderd.write_to_file(SAMPLE_OUTPUT_DMCO2.to_string());
+            result.1.curves_global_output.write_to_file(output_path(SAMPLE_OUTPUT_BLOATC));
+            result.0.curves_global_output.write_to_file(output_path(SAMPLE_OUTPUT_DMCO1));
+            result.0.curves_global_orderd.write_to_file(output_path(SAMPLE_OUTPUT_DMCO2));
         }}
     }
 
+    #[test]
+    fn svg_report_of_a_glyph() {
+        let sample_size = 64;
+        let dominants_recurrency = 12;
+        let input_data: Vmatrix<u32> = textfile_to_vmatrix(SAMPLE_INPUT_PATH.to_string(), sample_size);
+
+        let report = get_svg_report(&input_data, sample_size, dominants_recurrency);
+        let svg = report.to_svg();
+
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.trim_end().ends_with("</svg>"));
+        for layer in ["input", "dominant", "curves", "bloats"] {
+            assert!(svg.contains(&format!("<g id=\"{}\">", layer)));
+        }
+        assert!(svg.contains("<polyline"));
+        assert!(svg.contains("<circle"));
+        assert_eq!(svg.matches("<g ").count(), svg.matches("</g>").count());
+
+        // Curves stored along the columns are drawn as if they were along the rows
+        let (mut curve_data, _) = get_dominant_plus_bloat(&input_data, sample_size, dominants_recurrency);
+        let mut stored = SvgReport::new(sample_size, 10.0);
+        stored.add_curves(&curve_data);
+        curve_data.to_rows();
+        let mut along_rows = SvgReport::new(sample_size, 10.0);
+        along_rows.add_curves(&curve_data);
+        assert_eq!(stored.to_svg(), along_rows.to_svg());
+
+        // Every curve gets its own colour, and a single layer draws one square per entry with data
+        assert_ne!(curve_colour(1), curve_colour(2));
+        let mut single = SvgReport::new(3, 2.0);
+        single.add_matrix_layer("single", &Vmatrix::build(3, vec![1, 0, 0, 0, 1, 0, 0, 0, 0]), "#000", 1.0);
+        let svg = single.to_svg();
+        assert_eq!(svg.matches("<rect x=").count(), 2);
+        assert!(svg.contains("<rect x=\"2\" y=\"2\" width=\"2\" height=\"2\""));
+    }
+
+    #[test]
+    fn curves_back_to_rows() {
+        let sample_size = 64;
+        let dominants_recurrency = 12;
+        let input_data: Vmatrix<u32> = textfile_to_vmatrix(SAMPLE_INPUT_PATH.to_string(), sample_size);
+
+        let (mut curve_data, _) = get_dominant_plus_bloat(&input_data, sample_size, dominants_recurrency);
+        assert_eq!(curve_data.axis, Axis::COLUMNS);
+        let stored = curve_data.curves_global_output.clone();
+
+        curve_data.to_rows();
+        assert_eq!(curve_data.axis, Axis::ROWS);
+        assert_eq!(curve_data.curves_global_output.data, stored.transposed_copy().data);
+
+        // Already along the rows, nothing moves
+
+        let ordered = curve_data.curves_global_orderd.clone();
+        curve_data.to_rows();
+        assert_eq!(curve_data.curves_global_orderd.data, ordered.data);
+    }
+
+    #[test]
+    fn detect_lines_at_any_slope() {
+        let size = 20;
+        let mut input_data: Vmatrix<u32> = Vmatrix::initialize(size, 0);
+        for y in 2..12 {
+            input_data.set(y - 2, y, 1);
+        }
+        for x in 1..10 {
+            input_data.set(x, 14, 1);
+        }
+        // Two rows for every column
+        for y in 0..12 {
+            input_data.set(19 - y / 2, y, 1);
+        }
+        input_data.set(3, 18, 1);
+
+        let segments = detect_lines(&input_data, 6);
+        assert_eq!(segments.len(), 3);
+
+        assert_eq!((segments[0].start, segments[0].end), (Vector2::new(19, 0), Vector2::new(14, 11)));
+        assert_eq!(segments[0].length(), 12);
+        assert_eq!((segments[1].start, segments[1].end), (Vector2::new(0, 2), Vector2::new(9, 11)));
+        assert!((segments[1].theta - 3.0 * std::f64::consts::FRAC_PI_4).abs() < 1e-9);
+        assert_eq!((segments[2].start, segments[2].end), (Vector2::new(1, 14), Vector2::new(9, 14)));
+        assert_eq!(segments[2].theta, std::f64::consts::FRAC_PI_2);
+        assert_eq!(segments[2].rho, 14.0);
+
+        // Only the lone entry is left after removing the segments
+        let mask = segments_mask(&segments, size);
+        let remaining = input_data.xat(&mask);
+        assert_eq!(remaining.data.iter().sum::<u32>(), 1);
+        assert_eq!(remaining.get(3, 18), Some(1));
+
+        let sample_size = 64;
+        let kanji: Vmatrix<u32> = textfile_to_vmatrix(SAMPLE_INPUT_PATH.to_string(), sample_size);
+        for segment in detect_lines(&kanji, 12) {
+            assert!(segment.length() >= 12);
+            assert!(segment.cells.iter().all(|index| kanji.data[*index] != 0));
+        }
+    }
+
+    #[test]
+    fn recurrent_runs_as_segments() {
+        let size = 6;
+        let mut input_data: Vmatrix<u32> = Vmatrix::initialize(size, 0);
+        for x in 0..5 {
+            input_data.set(x, 1, 1);
+        }
+        for y in 0..6 {
+            input_data.set(5, y, 1);
+        }
+
+        // The run coming from the end of the first row is split, as it is on the mask
+        let rows = recurrent_runs(&input_data, 4, Axis::ROWS);
+        assert_eq!(rows.mask.data, recurrent_trace(&input_data, 4).data);
+        assert_eq!(rows.segments, vec![
+            RunSegment { axis: Axis::ROWS, line: 0, start: 5, end: 5, length: 1 },
+            RunSegment { axis: Axis::ROWS, line: 1, start: 0, end: 5, length: 6 },
+        ]);
+
+        let columns = recurrent_runs(&input_data, 4, Axis::COLUMNS);
+        assert_eq!(columns.segments, vec![RunSegment { axis: Axis::COLUMNS, line: 5, start: 0, end: 5, length: 6 }]);
+        assert_eq!(columns.segments[0].indexes(size), vec![5, 11, 17, 23, 29, 35]);
+        assert_eq!(columns.mask.data, recurrent_trace(&input_data.transposed_copy(), 4).transposed_copy().data);
+
+        // Painting the segments back gives the dominant masks
+        let sample_size = 64;
+        let dominants_recurrency = 12;
+        let kanji: Vmatrix<u32> = textfile_to_vmatrix(SAMPLE_INPUT_PATH.to_string(), sample_size);
+        let (along_rows, along_columns) = get_dominant_segments(&kanji, dominants_recurrency);
+
+        let vertical_dominant = recurrent_trace(&kanji, dominants_recurrency);
+        let horizont_dominant = recurrent_trace(&kanji.transposed_copy(), dominants_recurrency).transposed_copy();
+        for (segments, dominant) in [(along_rows, vertical_dominant), (along_columns, horizont_dominant)] {
+            let mut painted: Vmatrix<u32> = Vmatrix::initialize(sample_size, 0);
+            for segment in &segments {
+                assert_eq!(segment.length, segment.end - segment.start + 1);
+                for index in segment.indexes(sample_size) {
+                    painted.data[index] = 1;
+                }
+            }
+            assert!(!segments.is_empty());
+            assert_eq!(painted.data, dominant.data);
+        }
+    }
+
+    #[test]
+    fn distance_transform_metrics() {
+        // Pseudo random blobs, the same on every run
+        let size = 24;
+        let mut seed: u32 = 7;
+        let mut input_data: Vmatrix<u32> = Vmatrix::initialize(size, 0);
+        for i in 0..(size * size) {
+            seed = seed.wrapping_mul(1103515245).wrapping_add(12345);
+            input_data.data[i] = if (seed >> 16) % 5 == 0 { 0 } else { 1 };
+        }
+
+        for metric in [DistanceMetric::CHESSBOARD, DistanceMetric::CITYBLOCK, DistanceMetric::EUCLIDEAN] {
+            let distances = distance_transform(&input_data, metric);
+
+            for (x, y, value) in input_data.iter_coords() {
+                let mut expected = if value == 0 { 0.0 } else { f64::MAX };
+                if value != 0 {
+                    // Empty entries, including a frame around the matrix
+                    for empty_y in -1..=(size as i64) {
+                        for empty_x in -1..=(size as i64) {
+                            let inside = empty_x >= 0 && empty_y >= 0 && empty_x < size as i64 && empty_y < size as i64;
+                            if inside && input_data.get(empty_x as usize, empty_y as usize) != Some(0) {
+                                continue;
+                            }
+                            let dx = (empty_x - x as i64).abs() as f64;
+                            let dy = (empty_y - y as i64).abs() as f64;
+                            let distance = match metric {
+                                DistanceMetric::CHESSBOARD => dx.max(dy),
+                                DistanceMetric::CITYBLOCK => dx + dy,
+                                DistanceMetric::EUCLIDEAN => (dx * dx + dy * dy).sqrt(),
+                            };
+                            expected = expected.min(distance);
+                        }
+                    }
+                }
+                assert!((distances.get(x, y).unwrap() - expected).abs() < 1e-9, "{:?} at {} {}", metric, x, y);
+            }
+        }
+
+        let chessboard = integer_distance_transform(&input_data, DistanceMetric::CHESSBOARD);
+        assert_eq!(chessboard.data, distance_transform(&input_data, DistanceMetric::CHESSBOARD).data.iter().map(|value| *value as u32).collect::<Vec<u32>>());
+    }
+
+    #[test]
+    fn bloats_match_square_walk() {
+        let sample_size = 64;
+        let input_data: Vmatrix<u32> = textfile_to_vmatrix(SAMPLE_INPUT_PATH.to_string(), sample_size);
+
+        let mut global_data = GlobalCurveData::new(sample_size);
+        write_bloats(&mut global_data, &input_data);
+
+        // The sample has no holes within its strokes, so walking the squares finds the same radii
+        let expected = square_walk_bloats(&input_data, sample_size);
+        assert!(expected.iter().any(|value| *value > 1));
+        assert_eq!(global_data.curves_global_output.data, expected);
+    }
+
+    #[test]
+    fn bloats_around_a_hole() {
+        let size = 12;
+        let mut input_data: Vmatrix<u32> = Vmatrix::initialize(size, 0);
+        for y in 1..=9 {
+            for x in 1..=9 {
+                input_data.set(x, y, 1);
+            }
+        }
+        input_data.set(5, 4, 0);
+
+        // Walking only the outermost ring of every square steps over the hole
+        let walked = square_walk_bloats(&input_data, size);
+        assert_eq!(walked.iter().enumerate().filter(|(_, radius)| **radius != 0).collect::<Vec<_>>(), vec![(5 + 5 * size, &5)]);
+
+        let mut global_data = GlobalCurveData::new(size);
+        write_bloats(&mut global_data, &input_data);
+        let radii = &global_data.curves_global_output.data;
+        for y in 0..size {
+            let row = &radii[(y * size)..((y + 1) * size)];
+            match y {
+                2 => assert_eq!(row, [0, 0, 2, 2, 2, 2, 2, 2, 2, 0, 0, 0]),
+                3 | 4 => assert_eq!(row, [0, 0, 2, 2, 0, 0, 0, 2, 2, 0, 0, 0]),
+                7 => assert_eq!(row, [0, 0, 0, 3, 3, 3, 3, 3, 0, 0, 0, 0]),
+                _ => assert!(row.iter().all(|radius| *radius == 0)),
+            }
+        }
+
+        // Only squares full of data are kept, so they rebuild the block without filling the hole
+        assert_eq!(reconstruct_from_bloats(&bloat_disks(&global_data), size).data, input_data.data);
+    }
+
+    #[test]
+    fn bloat_disks_rebuild_the_shape() {
+        let size = 8;
+        let mut input_data: Vmatrix<u32> = Vmatrix::initialize(size, 0);
+        for y in 1..6 {
+            for x in 1..6 {
+                input_data.set(x, y, 1);
+            }
+        }
+
+        let disks = get_bloat_disks(input_data.data.clone(), size);
+        assert_eq!(disks, vec![(3 + 3 * size, 3)]);
+        assert_eq!(reconstruct_from_bloats(&disks, size).data, input_data.data);
+
+        // Bloats never cover more than the data, and describe it with far fewer entries
+        let sample_size = 64;
+        let kanji: Vmatrix<u32> = textfile_to_vmatrix(SAMPLE_INPUT_PATH.to_string(), sample_size);
+        let disks = get_bloat_disks(kanji.data.clone(), sample_size);
+        let rebuilt = reconstruct_from_bloats(&disks, sample_size);
+
+        let data_count = kanji.data.iter().filter(|value| **value != 0).count();
+        let rebuilt_count = rebuilt.data.iter().sum::<u32>() as usize;
+        assert!(rebuilt.xat(&kanji).data.iter().all(|value| *value == 0));
+        assert!(disks.len() < data_count);
+        assert!(rebuilt_count > disks.len() && rebuilt_count <= data_count);
+    }
+
+    #[test]
+    fn bloat_shapes_and_stroke_widths() {
+        // A diamond is a single bloat when walking diamonds
+        let size = 11;
+        let mut diamond: Vmatrix<u32> = Vmatrix::initialize(size, 0);
+        for (x, y, _) in diamond.clone().iter_coords() {
+            if x.abs_diff(5) + y.abs_diff(5) <= 2 {
+                diamond.set(x, y, 1);
+            }
+        }
+        let mut global_data = GlobalCurveData::new(size);
+        write_bloats_with_shape(&mut global_data, &diamond, BloatShape::DIAMOND);
+        let disks = bloat_disks(&global_data);
+        assert_eq!(disks, vec![(5 + 5 * size, 3)]);
+        assert_eq!(reconstruct_from_bloats_with_shape(&disks, size, BloatShape::DIAMOND).data, diamond.data);
+
+        // Every shape only rebuilds data
+        let sample_size = 64;
+        let kanji: Vmatrix<u32> = textfile_to_vmatrix(SAMPLE_INPUT_PATH.to_string(), sample_size);
+        for shape in [BloatShape::SQUARE, BloatShape::DIAMOND, BloatShape::DISC] {
+            let mut global_data = GlobalCurveData::new(sample_size);
+            write_bloats_with_shape(&mut global_data, &kanji, shape);
+            let rebuilt = reconstruct_from_bloats_with_shape(&bloat_disks(&global_data), sample_size, shape);
+            assert!(rebuilt.xat(&kanji).data.iter().all(|value| *value == 0));
+        }
+
+        // Curves found by a pipeline are on strokes at least 3 entries wide
+        let global_data = get_complete_inflexions_from_data(&kanji, sample_size, 12);
+        let widths = stroke_widths(&global_data, &kanji, BloatShape::SQUARE);
+        assert!(!widths.is_empty());
+        assert!(widths.iter().all(|(_, width)| *width >= 3.0));
+
+        // A stroke 5 entries wide along a row, and one 5 entries wide on every row along the diagonal, with the
+        // curves stored along the columns as the pipelines do
+        let size = 24;
+        let mut strokes: Vmatrix<u32> = Vmatrix::initialize(size, 0);
+        let mut global_data = GlobalCurveData::new_along(size, Axis::COLUMNS);
+        for x in 2..22 {
+            for y in 19..24 {
+                strokes.set(x, y, 1);
+            }
+        }
+        for (x, y, _) in strokes.clone().iter_coords() {
+            if y < 17 && x.abs_diff(y) <= 2 {
+                strokes.set(x, y, 1);
+            }
+        }
+        for i in 4..18 {
+            global_data.curves_global_output.set(21, i, 1);
+            global_data.curves_global_orderd.set(21, i, i as u32);
+        }
+        for i in 4..14 {
+            global_data.curves_global_output.set(i, i, 2);
+            global_data.curves_global_orderd.set(i, i, i as u32);
+        }
+
+        let square = stroke_widths(&global_data, &strokes, BloatShape::SQUARE);
+        let disc = stroke_widths(&global_data, &strokes, BloatShape::DISC);
+        assert_eq!(square[0], (1, 5.0));
+        assert_eq!(disc[0], (1, 5.0));
+
+        // The diagonal stroke is 5 / sqrt(2) wide, the disc gets closer to it than the square
+        let diagonal_width = 5.0 / std::f64::consts::SQRT_2;
+        assert_eq!(square[1].0, 2);
+        assert!((disc[1].1 - diagonal_width).abs() < (square[1].1 - diagonal_width).abs());
+        assert!((disc[1].1 - diagonal_width).abs() < 0.2);
+    }
+
+    #[test]
+    fn accumulation_from_distance() {
+        let sample_size = 64;
+        let kanji: Vmatrix<u32> = textfile_to_vmatrix(SAMPLE_INPUT_PATH.to_string(), sample_size);
+        let expected = get_accumulation(&kanji, &NullSink);
+        assert_eq!(get_accumulation_from_distance(&kanji).data, expected.data);
+        assert!(expected.data.iter().any(|value| *value > 1));
+
+        let transposed = kanji.transposed_copy();
+        assert_eq!(get_accumulation_from_distance(&transposed).data, get_accumulation(&transposed, &NullSink).data);
+
+        // Thick blobs decornered up to the limit, and thin data that stops after a single pass
+        let size = 40;
+        let mut seed: u32 = 11;
+        for threshold in [1, 40] {
+            let mut input_data: Vmatrix<u32> = Vmatrix::initialize(size, 1);
+            for i in 0..(size * size) {
+                seed = seed.wrapping_mul(1103515245).wrapping_add(12345);
+                if (seed >> 16) % 400 < threshold {
+                    input_data.data[i] = 0;
+                }
+            }
+            assert_eq!(get_accumulation_from_distance(&input_data).data, get_accumulation(&input_data, &NullSink).data);
+        }
+    }
+
+    #[test]
+    fn walk_along_columns_in_place() {
+        let sample_size = 64;
+        let recurrency = 12;
+        let kanji: Vmatrix<u32> = textfile_to_vmatrix(SAMPLE_INPUT_PATH.to_string(), sample_size);
+        let transposed = kanji.transposed_copy();
+
+        assert_eq!(Axis::COLUMNS.index_on(1, sample_size), sample_size);
+        assert_eq!(Axis::ROWS.index_on(1, sample_size), 1);
+
+        let horizont_dominant = recurrent_trace(&transposed, recurrency);
+        let in_place = recurrent_trace_along(&kanji, recurrency, Axis::COLUMNS);
+        assert_eq!(in_place.data, horizont_dominant.transposed_copy().data);
+        assert!(in_place.data.iter().any(|value| *value != 0));
+
+        // Curves on the transposed data, written on a transposed global
+        let mut expected_global = GlobalCurveData::new(sample_size);
+        let expected_curves = get_curves(&mut expected_global, &horizont_dominant);
+        let mut expected_marks = Vmatrix::initialize(sample_size, 0);
+        mark_curve_points(&expected_curves, &mut expected_marks, &mut expected_global, true);
+
+        let mut global_data = GlobalCurveData::new_along(sample_size, Axis::COLUMNS);
+        let curves = get_curves_along(&mut global_data, &in_place, Axis::COLUMNS);
+        let mut marks = Vmatrix::initialize(sample_size, 0);
+        mark_curve_points_along(&curves, &mut marks, &mut global_data, true, Axis::COLUMNS);
+
+        assert_eq!(curves.data, expected_curves.transposed_copy().data);
+        assert_eq!(marks.data, expected_marks.transposed_copy().data);
+        assert_eq!(global_data.curves_global_output.data, expected_global.curves_global_output.data);
+        assert_eq!(global_data.curves_global_orderd.data, expected_global.curves_global_orderd.data);
+        assert!(global_data.global_output_number > 1);
+    }
+
+    #[test]
+    fn bit_matrix_matches_vmatrix() {
+        let sample_size = 64;
+        let kanji: Vmatrix<u32> = textfile_to_vmatrix(SAMPLE_INPUT_PATH.to_string(), sample_size);
+
+        // Sizes that don't fill the last word, with blobs and thin noise
+        let mut samples = vec![kanji];
+        let mut seed: u32 = 7;
+        for (size, threshold) in [(37, 30), (37, 250), (70, 60), (3, 100)] {
+            let mut input_data: Vmatrix<u32> = Vmatrix::initialize(size, 1);
+            for i in 0..(size * size) {
+                seed = seed.wrapping_mul(1103515245).wrapping_add(12345);
+                if (seed >> 16) % 400 < threshold {
+                    input_data.data[i] = 0;
+                }
+            }
+            samples.push(input_data);
+        }
+
+        for input_data in &samples {
+            let bits = BitMatrix::from_vmatrix(input_data);
+            assert_eq!(bits.to_vmatrix().data, input_data.data);
+            assert_eq!(bits.count_ones(), input_data.data.iter().filter(|value| **value != 0).count());
+            assert_eq!(bits.transposed_copy().to_vmatrix().data, input_data.transposed_copy().data);
+
+            let other = input_data.transposed_copy();
+            let other_bits = BitMatrix::from_vmatrix(&other);
+            assert_eq!(bits.xat(&other_bits).to_vmatrix().data, input_data.xat(&other).data);
+            assert_eq!(bits.and(&other_bits).unwrap().to_vmatrix().data, input_data.and(&other).unwrap().data);
+            assert_eq!(bits.or(&other_bits).unwrap().to_vmatrix().data, input_data.or(&other).unwrap().data);
+            assert_eq!(bits.xor(&other_bits).unwrap().to_vmatrix().data, input_data.xor(&other).unwrap().data);
+            assert_eq!(bits.not().to_vmatrix().data, input_data.not().data);
+
+            let mut expected_flag = false;
+            let mut flag = true;
+            assert_eq!(decorner_once_bits(&bits, &mut flag).to_vmatrix().data, decorner_once(input_data, &mut expected_flag).data);
+            assert_eq!(flag, expected_flag);
+
+            for recurrency in [1, 2, 5, 12] {
+                for axis in [Axis::ROWS, Axis::COLUMNS] {
+                    let expected = recurrent_trace_along(input_data, recurrency, axis);
+                    assert_eq!(recurrent_trace_bits_along(&bits, recurrency, axis).to_vmatrix().data, expected.data);
+                }
+            }
+
+            for connectivity in [Connectivity::FOUR, Connectivity::EIGHT] {
+                let expected = label_components(input_data, connectivity);
+                let result = label_components_bits(&bits, connectivity);
+                assert_eq!(result.labels.data, expected.labels.data);
+                assert_eq!(result.components.len(), expected.components.len());
+                for (found, wanted) in result.components.iter().zip(expected.components.iter()) {
+                    assert_eq!(found.area, wanted.area);
+                    assert_eq!(found.bounding_min, wanted.bounding_min);
+                    assert_eq!(found.bounding_max, wanted.bounding_max);
+                    assert_eq!(found.centroid, wanted.centroid);
+                }
+            }
+        }
+
+        assert!(BitMatrix::new(5).and(&BitMatrix::new(6)).is_err());
+    }
+
+    #[test]
+    fn batches_keep_their_order() {
+        let sample_size = 64;
+        let dominants_recurrency = 12;
+        let kanji: Vmatrix<u32> = textfile_to_vmatrix(SAMPLE_INPUT_PATH.to_string(), sample_size);
+        let inputs = vec![kanji.clone(), kanji.transposed_copy(), Vmatrix::initialize(sample_size, 0), kanji];
+
+        let inflexions = get_complete_inflexions_from_batch(&inputs, sample_size, dominants_recurrency);
+        let combined = get_dominant_plus_bloat_from_batch(&inputs, sample_size, dominants_recurrency);
+        assert_eq!(inflexions.len(), inputs.len());
+        assert_eq!(combined.len(), inputs.len());
+
+        for (position, input_data) in inputs.iter().enumerate() {
+            let expected = get_complete_inflexions_from_data(input_data, sample_size, dominants_recurrency);
+            assert_eq!(inflexions[position].curves_global_output.data, expected.curves_global_output.data);
+            assert_eq!(inflexions[position].curves_global_orderd.data, expected.curves_global_orderd.data);
+
+            let (curves, bloats) = get_dominant_plus_bloat(input_data, sample_size, dominants_recurrency);
+            assert_eq!(combined[position].0.curves_global_output.data, curves.curves_global_output.data);
+            assert_eq!(combined[position].1.curves_global_output.data, bloats.curves_global_output.data);
+        }
+    }
+
+    #[test]
+    fn debug_artefacts_through_sinks() {
+        let sample_size = 64;
+        let kanji: Vmatrix<u32> = textfile_to_vmatrix(SAMPLE_INPUT_PATH.to_string(), sample_size);
+
+        let memory = MemorySink::new();
+        let accumulations = get_accumulation(&kanji, &memory);
+        assert_eq!(accumulations.data, get_accumulation(&kanji, &NullSink).data);
+
+        let reductions = memory.entries();
+        assert!(reductions.len() > 1);
+        assert_eq!(reductions[0].0, format!("{}1.txt", SAMPLE_OUTPUT_RED));
+        assert_eq!(reductions[0].1.lines().count(), sample_size);
+
+        let directory = Path::new(env!("CARGO_TARGET_TMPDIR")).join("reductions");
+        get_accumulation(&kanji, &DirectorySink::new(&directory));
+        for (name, content) in &reductions {
+            assert_eq!(&fs::read_to_string(directory.join(name)).unwrap(), content);
+        }
+    }
+
+    #[test]
+    fn matrices_through_readers_and_writers() {
+        let sample_size = 64;
+        let kanji: Vmatrix<u32> = textfile_to_vmatrix(SAMPLE_INPUT_PATH.to_string(), sample_size);
+
+        let mut written: Vec<u8> = Vec::new();
+        kanji.write_to(&mut written).unwrap();
+        assert_eq!(written, kanji.to_text().into_bytes());
+        assert_eq!(Vmatrix::read_from(written.as_slice(), sample_size).unwrap().data, kanji.data);
+
+        let path = output_path("saved_kanji.txt");
+        kanji.save(&path).unwrap();
+        assert_eq!(Vmatrix::load(&path, sample_size).unwrap().data, kanji.data);
+        assert_eq!(Vmatrix::load(SAMPLE_INPUT_PATH, sample_size).unwrap().data, kanji.data);
+
+        assert_eq!(Vmatrix::read_from(&b"01\n10\n"[..], 2).unwrap().data, vec![0, 1, 1, 0]);
+        let too_short = Vmatrix::read_from(&b"01\n1\n"[..], 2);
+        assert_eq!(too_short.unwrap_err().kind(), std::io::ErrorKind::InvalidData);
+        assert!(Vmatrix::load(output_path("missing_matrix.txt"), 2).is_err());
+    }
+
+    #[test]
+    fn geometric_transforms() {
+        let sample_data = Vmatrix::build(3, vec![1, 2, 3,
+                                                 4, 5, 6,
+                                                 7, 8, 9]);
+
+        assert_eq!(sample_data.rotated_copy(Rotation::RIGHT).data, vec![7, 4, 1, 8, 5, 2, 9, 6, 3]);
+        assert_eq!(sample_data.rotated_copy(Rotation::HALF).data, vec![9, 8, 7, 6, 5, 4, 3, 2, 1]);
+        assert_eq!(sample_data.rotated_copy(Rotation::LEFT).data, vec![3, 6, 9, 2, 5, 8, 1, 4, 7]);
+        assert_eq!(sample_data.flipped_horizontal().data, vec![3, 2, 1, 6, 5, 4, 9, 8, 7]);
+        assert_eq!(sample_data.flipped_vertical().data, vec![7, 8, 9, 4, 5, 6, 1, 2, 3]);
+
+        assert_eq!(sample_data.translated(1, -1, 0).data, vec![0, 4, 5, 0, 7, 8, 0, 0, 0]);
+        assert_eq!(sample_data.translated(-3, 0, 0).data, vec![0; 9]);
+        assert_eq!(sample_data.cropped(1, 1, 2).unwrap().data, vec![5, 6, 8, 9]);
+        assert!(sample_data.cropped(2, 0, 2).is_none());
+        assert!(sample_data.cropped(usize::MAX, 0, 2).is_none());
+        assert_eq!(sample_data.translated(isize::MIN, isize::MAX, 0).data, vec![0; 9]);
+        assert_eq!(sample_data.padded(1, 0).data, vec![0, 0, 0, 0, 0,
+                                                       0, 1, 2, 3, 0,
+                                                       0, 4, 5, 6, 0,
+                                                       0, 7, 8, 9, 0,
+                                                       0, 0, 0, 0, 0]);
+
+        // Turning right follows the same indexes as transposing and flipping
+        let sample_size = 64;
+        let kanji: Vmatrix<u32> = textfile_to_vmatrix(SAMPLE_INPUT_PATH.to_string(), sample_size);
+        let right = kanji.rotated_copy(Rotation::RIGHT);
+        assert_eq!(right.data, kanji.transposed_copy().flipped_horizontal().data);
+        assert_eq!(right.rotated_copy(Rotation::LEFT).data, kanji.data);
+        assert_eq!(right.rotated_copy(Rotation::RIGHT).data, kanji.rotated_copy(Rotation::HALF).data);
+        assert_eq!(kanji.rotated_copy(Rotation::HALF).data, kanji.flipped_horizontal().flipped_vertical().data);
+
+        let (minimum, maximum) = kanji.content_bounds().unwrap();
+        let labelled = label_components(&kanji, Connectivity::EIGHT);
+        assert_eq!(minimum.x, labelled.components.iter().map(|component| component.bounding_min.x).min().unwrap());
+        assert_eq!(maximum.y, labelled.components.iter().map(|component| component.bounding_max.y).max().unwrap());
+
+        let content = kanji.cropped_to_content(0);
+        assert_eq!(content.size, (maximum.x - minimum.x).max(maximum.y - minimum.y) + 1);
+        assert_eq!(content.content_bounds().unwrap().0, Vector2::new(0, 0));
+        assert_eq!(content.data.iter().sum::<u32>(), kanji.data.iter().sum::<u32>());
+
+        let moved = kanji.translated(-(minimum.x as isize), -(minimum.y as isize), 0);
+        assert_eq!(moved.cropped(0, 0, content.size).unwrap().data, content.data);
+        assert_eq!(kanji.padded(3, 0).cropped(3, 3, sample_size).unwrap().data, kanji.data);
+        assert_eq!(Vmatrix::<u32>::initialize(4, 0).cropped_to_content(0).size, 0);
+    }
+
 }
\ No newline at end of file